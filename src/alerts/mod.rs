@@ -0,0 +1,359 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+pub mod sinks;
+
+const HISTORY_LEN: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    /// Transactions per second
+    Tps,
+    /// Network utilization in %
+    NetworkUtil,
+    /// MPC node CPU usage in %
+    NodeCpu,
+    /// MPC node upstream throughput in Mbps
+    NodeNetUp,
+    /// MPC node downstream throughput in Mbps
+    NodeNetDown,
+    TotalTxs,
+    /// Average transaction latency in seconds
+    AvgTxLatency,
+    /// Average coSNARK generation time in seconds
+    AvgProofTime,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Metric::Tps => "tps",
+            Metric::NetworkUtil => "network_util",
+            Metric::NodeCpu => "node_cpu",
+            Metric::NodeNetUp => "node_net_up",
+            Metric::NodeNetDown => "node_net_down",
+            Metric::TotalTxs => "total_txs",
+            Metric::AvgTxLatency => "avg_tx_latency",
+            Metric::AvgProofTime => "avg_proof_time",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Comparison {
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AboveOrEqual,
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    BelowOrEqual,
+}
+
+impl Comparison {
+    fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::AboveOrEqual => value >= threshold,
+            Comparison::Below => value < threshold,
+            Comparison::BelowOrEqual => value <= threshold,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Comparison::Above => ">",
+            Comparison::AboveOrEqual => ">=",
+            Comparison::Below => "<",
+            Comparison::BelowOrEqual => "<=",
+        };
+        f.write_str(op)
+    }
+}
+
+/// An alert rule as read from the `[[alerts]]` tables of the config.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    pub metric: Metric,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    /// Network the rule applies to, all networks if unset
+    #[serde(default)]
+    pub network: Option<String>,
+    /// How long the condition has to hold before the alert fires
+    #[serde(default)]
+    pub duration_secs: u64,
+}
+
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.metric, self.comparison, self.threshold)?;
+        if self.duration_secs > 0 {
+            write!(f, " for {}s", self.duration_secs)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Ok,
+    Pending,
+    Firing,
+}
+
+pub struct RuleState {
+    pub rule: AlertRule,
+    pub network: String,
+    pub status: AlertStatus,
+    pub value: Option<f64>,
+    pending_since: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertEventKind {
+    Firing,
+    Resolved,
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub network: String,
+    pub condition: String,
+    pub kind: AlertEventKind,
+    pub value: f64,
+    pub at: DateTime<Local>,
+}
+
+/// Evaluates the configured alert rules against incoming metric values.
+pub struct AlertEngine {
+    pub rules: Vec<RuleState>,
+    /// Firing and resolved events, newest first
+    pub history: VecDeque<AlertEvent>,
+    /// Last error reported by an alert sink
+    pub delivery_error: Option<String>,
+}
+
+impl AlertEngine {
+    /// Creates the engine with one rule state per rule and matching network.
+    ///
+    /// Fails if a rule names a network that is not configured.
+    pub fn new(rules: Vec<AlertRule>, networks: &[String]) -> eyre::Result<Self> {
+        if let Some(rule) = rules.iter().find(|rule| {
+            rule.network
+                .as_ref()
+                .is_some_and(|network| !networks.contains(network))
+        }) {
+            eyre::bail!(
+                "alert rule `{}` refers to unknown network `{}`",
+                rule.name,
+                rule.network.as_deref().unwrap_or_default()
+            );
+        }
+        Ok(Self {
+            rules: rules
+                .into_iter()
+                .flat_map(|rule| {
+                    networks
+                        .iter()
+                        .filter(|network| rule.network.as_ref().is_none_or(|n| n == *network))
+                        .map(|network| RuleState {
+                            rule: rule.clone(),
+                            network: network.clone(),
+                            status: AlertStatus::Ok,
+                            value: None,
+                            pending_since: None,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+            history: VecDeque::new(),
+            delivery_error: None,
+        })
+    }
+
+    pub fn firing(&self) -> usize {
+        self.rules
+            .iter()
+            .filter(|state| state.status == AlertStatus::Firing)
+            .count()
+    }
+
+    /// Evaluates all rules and returns the events of alerts that changed state.
+    pub fn evaluate(&mut self, value_of: impl Fn(&str, Metric) -> Option<f64>) -> Vec<AlertEvent> {
+        self.evaluate_at(Instant::now(), value_of)
+    }
+
+    fn evaluate_at(
+        &mut self,
+        now: Instant,
+        value_of: impl Fn(&str, Metric) -> Option<f64>,
+    ) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        for state in &mut self.rules {
+            let Some(value) = value_of(&state.network, state.rule.metric) else {
+                continue;
+            };
+            state.value = Some(value);
+            if state.rule.comparison.holds(value, state.rule.threshold) {
+                let since = *state.pending_since.get_or_insert(now);
+                if state.status != AlertStatus::Firing {
+                    if now.duration_since(since) >= Duration::from_secs(state.rule.duration_secs) {
+                        state.status = AlertStatus::Firing;
+                        events.push(event(state, AlertEventKind::Firing, value));
+                    } else {
+                        state.status = AlertStatus::Pending;
+                    }
+                }
+            } else {
+                state.pending_since = None;
+                if state.status == AlertStatus::Firing {
+                    events.push(event(state, AlertEventKind::Resolved, value));
+                }
+                state.status = AlertStatus::Ok;
+            }
+        }
+        for event in &events {
+            self.history.push_front(event.clone());
+        }
+        self.history.truncate(HISTORY_LEN);
+        events
+    }
+}
+
+fn event(state: &RuleState, kind: AlertEventKind, value: f64) -> AlertEvent {
+    AlertEvent {
+        rule: state.rule.name.clone(),
+        network: state.network.clone(),
+        condition: state.rule.to_string(),
+        kind,
+        value,
+        at: Local::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(network: Option<&str>, duration_secs: u64) -> AlertRule {
+        AlertRule {
+            name: "high tps".to_string(),
+            metric: Metric::Tps,
+            comparison: Comparison::Above,
+            threshold: 100.0,
+            network: network.map(str::to_string),
+            duration_secs,
+        }
+    }
+
+    fn networks() -> Vec<String> {
+        vec!["Base".to_string(), "Arc".to_string()]
+    }
+
+    fn tps(value: f64) -> impl Fn(&str, Metric) -> Option<f64> {
+        move |network, metric| (network == "Base" && metric == Metric::Tps).then_some(value)
+    }
+
+    #[test]
+    fn rejects_rules_for_unknown_networks() {
+        let error = AlertEngine::new(vec![rule(Some("Mainnet"), 0)], &networks())
+            .err()
+            .expect("unknown network accepted");
+        assert!(error.to_string().contains("unknown network `Mainnet`"));
+
+        let engine = AlertEngine::new(vec![rule(None, 0)], &networks()).unwrap();
+        assert_eq!(engine.rules.len(), 2);
+        let engine = AlertEngine::new(vec![rule(Some("Arc"), 0)], &networks()).unwrap();
+        assert_eq!(engine.rules.len(), 1);
+        assert_eq!(engine.rules[0].network, "Arc");
+    }
+
+    #[test]
+    fn fires_once_the_condition_held_for_the_duration() {
+        let mut engine = AlertEngine::new(vec![rule(Some("Base"), 30)], &networks()).unwrap();
+        let start = Instant::now();
+
+        assert!(engine.evaluate_at(start, tps(150.0)).is_empty());
+        assert_eq!(engine.rules[0].status, AlertStatus::Pending);
+
+        let almost = start + Duration::from_secs(30) - Duration::from_millis(1);
+        assert!(engine.evaluate_at(almost, tps(150.0)).is_empty());
+        assert_eq!(engine.rules[0].status, AlertStatus::Pending);
+
+        let events = engine.evaluate_at(start + Duration::from_secs(30), tps(150.0));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, AlertEventKind::Firing);
+        assert_eq!(events[0].network, "Base");
+        assert_eq!(engine.rules[0].status, AlertStatus::Firing);
+        assert_eq!(engine.firing(), 1);
+
+        // staying above the threshold does not fire again
+        assert!(
+            engine
+                .evaluate_at(start + Duration::from_secs(60), tps(150.0))
+                .is_empty()
+        );
+        assert_eq!(engine.history.len(), 1);
+    }
+
+    #[test]
+    fn pending_resets_when_the_condition_stops_holding() {
+        let mut engine = AlertEngine::new(vec![rule(Some("Base"), 30)], &networks()).unwrap();
+        let start = Instant::now();
+
+        engine.evaluate_at(start, tps(150.0));
+        assert!(
+            engine
+                .evaluate_at(start + Duration::from_secs(20), tps(50.0))
+                .is_empty()
+        );
+        assert_eq!(engine.rules[0].status, AlertStatus::Ok);
+
+        // the duration counts from when the condition holds again
+        engine.evaluate_at(start + Duration::from_secs(25), tps(150.0));
+        assert!(
+            engine
+                .evaluate_at(start + Duration::from_secs(40), tps(150.0))
+                .is_empty()
+        );
+        assert_eq!(engine.rules[0].status, AlertStatus::Pending);
+    }
+
+    #[test]
+    fn resolves_a_firing_alert() {
+        let mut engine = AlertEngine::new(vec![rule(Some("Base"), 0)], &networks()).unwrap();
+        let start = Instant::now();
+
+        let events = engine.evaluate_at(start, tps(150.0));
+        assert_eq!(events[0].kind, AlertEventKind::Firing);
+
+        // no value keeps the state as is
+        assert!(engine.evaluate_at(start, |_, _| None).is_empty());
+        assert_eq!(engine.rules[0].status, AlertStatus::Firing);
+
+        let events = engine.evaluate_at(start + Duration::from_secs(1), tps(50.0));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, AlertEventKind::Resolved);
+        assert_eq!(events[0].value, 50.0);
+        assert_eq!(engine.rules[0].status, AlertStatus::Ok);
+        assert_eq!(engine.firing(), 0);
+
+        let kinds: Vec<_> = engine.history.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, [AlertEventKind::Resolved, AlertEventKind::Firing]);
+    }
+}
@@ -0,0 +1,421 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, Write as _},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use eyre::{Context as _, eyre};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::alerts::{AlertEvent, AlertEventKind};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a command sink may run before it is killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SinkKind {
+    /// Runs `command` with `sh -c`, passing the alert as JSON on stdin and as
+    /// `MERCES_ALERT_*` environment variables.
+    Command { command: String },
+    /// POSTs the alert as JSON to `url`.
+    Webhook { url: String },
+}
+
+/// An alert sink as read from the `[[alert_sinks]]` tables of the config.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SinkConfig {
+    #[serde(flatten)]
+    pub kind: SinkKind,
+    /// Repeats of the last delivered state of a rule within this window are
+    /// not delivered
+    #[serde(default = "default_dedup_secs")]
+    pub dedup_secs: u64,
+    /// Upper bound of deliveries to this sink per minute
+    #[serde(default = "default_max_per_minute")]
+    pub max_per_minute: usize,
+}
+
+fn default_dedup_secs() -> u64 {
+    5 * 60
+}
+
+fn default_max_per_minute() -> usize {
+    10
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    rule: &'a str,
+    network: &'a str,
+    condition: &'a str,
+    state: &'a str,
+    value: f64,
+    at: String,
+}
+
+impl<'a> From<&'a AlertEvent> for Payload<'a> {
+    fn from(event: &'a AlertEvent) -> Self {
+        Self {
+            rule: &event.rule,
+            network: &event.network,
+            condition: &event.condition,
+            state: match event.kind {
+                AlertEventKind::Firing => "firing",
+                AlertEventKind::Resolved => "resolved",
+            },
+            value: event.value,
+            at: event.at.to_rfc3339(),
+        }
+    }
+}
+
+struct Sink {
+    config: SinkConfig,
+    /// Last delivered state per rule and network
+    delivered: HashMap<(String, String), (AlertEventKind, Instant)>,
+    recent: VecDeque<Instant>,
+}
+
+impl Sink {
+    fn new(config: SinkConfig) -> Self {
+        Self {
+            config,
+            delivered: HashMap::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Applies deduplication and rate limiting, returns whether `event` may be delivered.
+    fn admit(&mut self, event: &AlertEvent) -> bool {
+        let now = Instant::now();
+        let key = (event.rule.clone(), event.network.clone());
+        let dedup = Duration::from_secs(self.config.dedup_secs);
+        if self
+            .delivered
+            .get(&key)
+            .is_some_and(|(kind, last)| *kind == event.kind && now.duration_since(*last) < dedup)
+        {
+            debug!(rule = %event.rule, network = %event.network, "alert deduplicated");
+            return false;
+        }
+        while self
+            .recent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= Duration::from_secs(60))
+        {
+            self.recent.pop_front();
+        }
+        if self.recent.len() >= self.config.max_per_minute {
+            debug!(rule = %event.rule, network = %event.network, "alert rate limited");
+            return false;
+        }
+        self.delivered.insert(key, (event.kind, now));
+        self.recent.push_back(now);
+        true
+    }
+
+    fn deliver(&self, event: &AlertEvent) -> eyre::Result<()> {
+        let payload = Payload::from(event);
+        match &self.config.kind {
+            SinkKind::Command { command } => {
+                let mut child = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env("MERCES_ALERT_RULE", payload.rule)
+                    .env("MERCES_ALERT_NETWORK", payload.network)
+                    .env("MERCES_ALERT_CONDITION", payload.condition)
+                    .env("MERCES_ALERT_STATE", payload.state)
+                    .env("MERCES_ALERT_VALUE", payload.value.to_string())
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .wrap_err_with(|| format!("cannot run `{command}`"))?;
+                // a command that does not read stdin may exit before the
+                // payload is written, which is not a failed delivery
+                let written = child.stdin.take().map_or(Ok(()), |mut stdin| {
+                    serde_json::to_writer(&mut stdin, &payload)
+                        .map_err(io::Error::from)
+                        .and_then(|()| stdin.write_all(b"\n"))
+                });
+                let status = wait(&mut child, command)?;
+                if let Err(err) = written
+                    && err.kind() != io::ErrorKind::BrokenPipe
+                {
+                    return Err(err).wrap_err_with(|| format!("cannot write to `{command}`"));
+                }
+                if !status.success() {
+                    return Err(eyre!("`{command}` exited with {status}"));
+                }
+            }
+            SinkKind::Webhook { url } => {
+                let agent: ureq::Agent = ureq::Agent::config_builder()
+                    .timeout_global(Some(WEBHOOK_TIMEOUT))
+                    .build()
+                    .into();
+                agent
+                    .post(url)
+                    .send_json(&payload)
+                    .wrap_err_with(|| format!("webhook {url} failed"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Waits for `child` to exit, killing it once it ran for [`COMMAND_TIMEOUT`].
+///
+/// The child is reaped on every path so it never lingers as a zombie.
+fn wait(child: &mut Child, command: &str) -> eyre::Result<ExitStatus> {
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) if started.elapsed() < COMMAND_TIMEOUT => {
+                thread::sleep(Duration::from_millis(50));
+            }
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(eyre!(
+                    "`{command}` timed out after {}s",
+                    COMMAND_TIMEOUT.as_secs()
+                ));
+            }
+            Err(err) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(err).wrap_err_with(|| format!("cannot wait for `{command}`"));
+            }
+        }
+    }
+}
+
+/// Delivers alert events to the configured sinks on a background thread.
+pub struct AlertDispatcher {
+    events: Sender<AlertEvent>,
+    results: Receiver<Result<(), String>>,
+    worker: JoinHandle<()>,
+}
+
+impl AlertDispatcher {
+    pub fn new(sinks: Vec<SinkConfig>) -> Self {
+        let (events, event_rx) = mpsc::channel::<AlertEvent>();
+        let (result_tx, results) = mpsc::channel();
+        let mut sinks = sinks.into_iter().map(Sink::new).collect::<Vec<_>>();
+        let worker = thread::spawn(move || {
+            for event in event_rx {
+                for sink in &mut sinks {
+                    if !sink.admit(&event) {
+                        continue;
+                    }
+                    let result = match sink.deliver(&event) {
+                        Ok(()) => {
+                            debug!(rule = %event.rule, "alert delivered");
+                            Ok(())
+                        }
+                        Err(err) => {
+                            warn!(rule = %event.rule, "alert delivery failed: {err:#}");
+                            Err(format!("{err:#}"))
+                        }
+                    };
+                    let _ = result_tx.send(result);
+                }
+            }
+        });
+        Self {
+            events,
+            results,
+            worker,
+        }
+    }
+
+    pub fn dispatch(&self, events: &[AlertEvent]) {
+        for event in events {
+            // the worker only stops once the dispatcher is shut down or dropped
+            let _ = self.events.send(event.clone());
+        }
+    }
+
+    /// Stops accepting events and waits until the dispatched ones are
+    /// delivered.
+    pub fn shutdown(self) {
+        drop(self.events);
+        if self.worker.join().is_err() {
+            warn!("alert delivery thread panicked");
+        }
+    }
+
+    /// Returns the result of the most recent delivery since the last call,
+    /// if any.
+    pub fn last_result(&self) -> Option<Result<(), String>> {
+        self.results.try_iter().last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read},
+        net::TcpListener,
+    };
+
+    use chrono::Local;
+
+    use super::*;
+
+    fn sink(kind: SinkKind, dedup_secs: u64, max_per_minute: usize) -> Sink {
+        Sink::new(SinkConfig {
+            kind,
+            dedup_secs,
+            max_per_minute,
+        })
+    }
+
+    fn event(rule: &str, kind: AlertEventKind) -> AlertEvent {
+        AlertEvent {
+            rule: rule.to_string(),
+            network: "Mainnet".to_string(),
+            condition: "tps > 10".to_string(),
+            kind,
+            value: 12.5,
+            at: Local::now(),
+        }
+    }
+
+    fn command(command: &str) -> SinkKind {
+        SinkKind::Command {
+            command: command.to_string(),
+        }
+    }
+
+    #[test]
+    fn admit_suppresses_repeats_of_the_last_state() {
+        let mut sink = sink(command("true"), 60, 10);
+        assert!(sink.admit(&event("tps", AlertEventKind::Firing)));
+        assert!(!sink.admit(&event("tps", AlertEventKind::Firing)));
+        assert!(sink.admit(&event("tps", AlertEventKind::Resolved)));
+        // firing again after it resolved is a change of state
+        assert!(sink.admit(&event("tps", AlertEventKind::Firing)));
+        assert!(sink.admit(&event("cpu", AlertEventKind::Firing)));
+    }
+
+    #[test]
+    fn admit_delivers_repeats_after_the_dedup_window() {
+        let mut sink = sink(command("true"), 0, 10);
+        assert!(sink.admit(&event("tps", AlertEventKind::Firing)));
+        assert!(sink.admit(&event("tps", AlertEventKind::Firing)));
+    }
+
+    #[test]
+    fn admit_applies_the_rate_limit() {
+        let mut sink = sink(command("true"), 60, 2);
+        assert!(sink.admit(&event("a", AlertEventKind::Firing)));
+        assert!(sink.admit(&event("b", AlertEventKind::Firing)));
+        assert!(!sink.admit(&event("c", AlertEventKind::Firing)));
+        // a rate-limited event is not recorded as delivered
+        sink.recent.clear();
+        assert!(sink.admit(&event("c", AlertEventKind::Firing)));
+    }
+
+    #[test]
+    fn command_receives_the_event_on_stdin_and_in_the_environment() {
+        let path = std::env::temp_dir().join(format!("merces-sink-{}", std::process::id()));
+        let sink = sink(
+            command(&format!(
+                "cat > '{0}' && printf '%s %s' \"$MERCES_ALERT_STATE\" \"$MERCES_ALERT_VALUE\" >> '{0}'",
+                path.display()
+            )),
+            0,
+            10,
+        );
+        sink.deliver(&event("tps", AlertEventKind::Resolved))
+            .unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let (json, env) = written.split_once('\n').unwrap();
+        let payload: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(payload["rule"], "tps");
+        assert_eq!(payload["state"], "resolved");
+        assert_eq!(env, "resolved 12.5");
+    }
+
+    #[test]
+    fn command_that_ignores_stdin_is_delivered() {
+        // `true` may exit before the payload is written
+        let sink = sink(command("true"), 0, 10);
+        for _ in 0..20 {
+            sink.deliver(&event("tps", AlertEventKind::Firing)).unwrap();
+        }
+    }
+
+    #[test]
+    fn command_failure_is_reported() {
+        let sink = sink(command("exit 3"), 0, 10);
+        let err = sink
+            .deliver(&event("tps", AlertEventKind::Firing))
+            .unwrap_err();
+        assert!(err.to_string().contains("exited with"));
+    }
+
+    #[test]
+    fn command_is_killed_after_the_timeout() {
+        let sink = sink(command("sleep 60"), 0, 10);
+        let started = Instant::now();
+        let err = sink
+            .deliver(&event("tps", AlertEventKind::Firing))
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        assert!(started.elapsed() < COMMAND_TIMEOUT + Duration::from_secs(5));
+    }
+
+    #[test]
+    fn webhook_posts_the_event_as_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .unwrap();
+            (request_line, body)
+        });
+
+        let sink = sink(SinkKind::Webhook { url }, 0, 10);
+        sink.deliver(&event("tps", AlertEventKind::Firing)).unwrap();
+
+        let (request_line, body) = server.join().unwrap();
+        assert!(request_line.starts_with("POST /alerts "));
+        let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(payload["rule"], "tps");
+        assert_eq!(payload["network"], "Mainnet");
+        assert_eq!(payload["state"], "firing");
+        assert_eq!(payload["value"], 12.5);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use eyre::Context as _;
+use serde::Deserialize;
+
+use crate::{
+    alerts::{AlertRule, sinks::SinkConfig},
+    export::ExportFormat,
+    keymap::KeymapConfig,
+    network::NetworkConfig,
+    screenshot::ScreenshotFormat,
+    theme::Theme,
+    widgets::logo::LogoMode,
+};
+
+pub(crate) const APP_DIR: &str = "taceo-merces-tui";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub history: HistoryConfig,
+    pub networks: Vec<NetworkConfig>,
+    pub alerts: Vec<AlertRule>,
+    pub alert_sinks: Vec<SinkConfig>,
+    pub keymap: KeymapConfig,
+    /// Name of the theme used at startup
+    pub theme: Option<String>,
+    /// Custom themes in addition to the built-in ones
+    pub themes: Vec<Theme>,
+    pub logo: LogoMode,
+    /// Format of the screenshots taken with the screenshot key
+    pub screenshot: ScreenshotFormat,
+    /// Format of the data exported with the export key
+    pub export: ExportFormat,
+    /// Directory screenshots and exports are written to
+    pub output_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Loads the config from `path`, or from the default location if `None`.
+    ///
+    /// A missing file at the default location yields the default config.
+    pub fn load(path: Option<&Path>) -> eyre::Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match dirs::config_dir() {
+                Some(dir) => (dir.join(APP_DIR).join("config.toml"), false),
+                None => return Ok(Self::default()),
+            },
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .wrap_err_with(|| format!("invalid config file {}", path.display())),
+            Err(err) if !required && err.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            Err(err) => {
+                Err(err).wrap_err_with(|| format!("cannot read config file {}", path.display()))
+            }
+        }
+    }
+
+    /// The directory screenshots and exports are written to, `exports` in the
+    /// local data directory unless configured.
+    pub fn output_dir(&self) -> PathBuf {
+        self.output_dir
+            .clone()
+            .or_else(|| dirs::data_local_dir().map(|dir| dir.join(APP_DIR).join("exports")))
+            .unwrap_or_else(|| std::env::temp_dir().join(APP_DIR))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    pub enabled: bool,
+    pub dir: Option<PathBuf>,
+    pub retention_hours: u64,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            retention_hours: 6,
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// The history root, every network keeps its streams in a subdirectory.
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.dir
+            .clone()
+            .or_else(|| dirs::data_local_dir().map(|dir| dir.join(APP_DIR).join("history")))
+    }
+
+    pub fn retention(&self) -> Duration {
+        Duration::from_secs(self.retention_hours * 60 * 60)
+    }
+}
@@ -0,0 +1,97 @@
+//! Terminal restoration and crash reports on panic.
+//!
+//! The event loop records a summary of the app state and the latest input
+//! events here, so a panic anywhere can write them to a report file.
+
+use std::{
+    backtrace::Backtrace,
+    collections::VecDeque,
+    fmt::Write as _,
+    panic::PanicHookInfo,
+    path::PathBuf,
+    sync::{Mutex, MutexGuard, PoisonError},
+    thread,
+};
+
+use chrono::Local;
+use ratatui::crossterm::{event::DisableMouseCapture, execute};
+use tracing::error;
+
+use crate::config::APP_DIR;
+
+/// Number of input events kept for the report.
+const MAX_EVENTS: usize = 50;
+
+static STATE: Mutex<String> = Mutex::new(String::new());
+static EVENTS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Replaces the app state summary written to crash reports.
+pub fn set_state(summary: String) {
+    *lock(&STATE) = summary;
+}
+
+/// Remembers `event` for crash reports, dropping the oldest one when full.
+pub fn record_event(event: impl std::fmt::Debug) {
+    let mut events = lock(&EVENTS);
+    if events.len() == MAX_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(format!("{} {event:?}", Local::now().format("%H:%M:%S%.3f")));
+}
+
+/// Installs a panic hook that restores the terminal, writes a crash report
+/// and prints its path before the panic message.
+///
+/// Only panics of the calling thread, which runs the UI, end the app. Panics
+/// of worker threads are logged and leave the terminal to the UI.
+///
+/// Must be called after `ratatui::init`, whose hook is chained.
+pub fn install_hook() {
+    let previous = std::panic::take_hook();
+    let main = thread::current().id();
+    std::panic::set_hook(Box::new(move |info| {
+        let current = thread::current();
+        if current.id() != main {
+            error!(
+                thread = current.name().unwrap_or("unnamed"),
+                "worker thread panicked: {info}"
+            );
+            return;
+        }
+        let _ = execute!(std::io::stdout(), DisableMouseCapture);
+        ratatui::restore();
+        match write_report(info) {
+            Ok(path) => eprintln!("Crash report written to {}", path.display()),
+            Err(err) => eprintln!("Cannot write crash report: {err}"),
+        }
+        previous(info);
+    }));
+}
+
+fn write_report(info: &PanicHookInfo) -> std::io::Result<PathBuf> {
+    let dir = dirs::data_local_dir()
+        .map(|dir| dir.join(APP_DIR).join("crashes"))
+        .unwrap_or_else(std::env::temp_dir);
+    std::fs::create_dir_all(&dir)?;
+    let now = Local::now();
+    let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+
+    let mut report = format!(
+        "{} {} crashed at {}\n\n{info}\n\nState:\n{}\n\nRecent events, oldest first:\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339(),
+        lock(&STATE),
+    );
+    for event in lock(&EVENTS).iter() {
+        let _ = writeln!(report, "{event}");
+    }
+    let _ = write!(report, "\nBacktrace:\n{}", Backtrace::force_capture());
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Locks `mutex` even if a panic poisoned it, the report is best effort.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,155 @@
+//! Internal tracing of the app.
+//!
+//! Events are written to a daily rotated log file, never to the terminal,
+//! and the most recent ones are kept in memory for the Diagnostics page.
+
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chrono::{DateTime, Local};
+use eyre::Context as _;
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+    warn,
+};
+use tracing_appender::{non_blocking::WorkerGuard, rolling};
+use tracing_subscriber::{
+    filter::{LevelFilter, Targets},
+    layer::{Context, Layer, SubscriberExt as _},
+    util::SubscriberInitExt as _,
+};
+
+use crate::config::APP_DIR;
+
+/// Number of events kept for the Diagnostics page.
+const MAX_EVENTS: usize = 200;
+/// Number of daily log files kept.
+const MAX_LOG_FILES: usize = 7;
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticEvent {
+    pub at: DateTime<Local>,
+    pub level: Level,
+    pub target: &'static str,
+    pub message: String,
+}
+
+/// Handle to the recent internal events, shared with the tracing layer.
+#[derive(Clone)]
+pub struct Diagnostics {
+    events: Arc<Mutex<VecDeque<DiagnosticEvent>>>,
+    /// Directory of the log files
+    pub log_dir: PathBuf,
+    /// Why events are not written to the log files, if they are not
+    pub log_error: Option<String>,
+}
+
+impl Diagnostics {
+    /// Sets up tracing to the log files and returns the handle to the recent
+    /// events, together with the guard that flushes the files when dropped.
+    ///
+    /// `verbosity` raises the level of the app's own events from info to
+    /// debug and trace, other crates only log warnings.
+    ///
+    /// If the log files cannot be opened, events are only kept in memory and
+    /// the error is kept in [`Diagnostics::log_error`].
+    pub fn init(verbosity: u8) -> (Self, Option<WorkerGuard>) {
+        let log_dir = dirs::data_local_dir()
+            .map(|dir| dir.join(APP_DIR).join("logs"))
+            .unwrap_or_else(|| std::env::temp_dir().join(APP_DIR));
+        let appender = rolling::Builder::new()
+            .rotation(rolling::Rotation::DAILY)
+            .filename_prefix(APP_DIR)
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(&log_dir)
+            .wrap_err_with(|| format!("cannot open log files in {}", log_dir.display()));
+        let (file_layer, guard, log_error) = match appender {
+            Ok(appender) => {
+                let (writer, guard) = tracing_appender::non_blocking(appender);
+                let layer = tracing_subscriber::fmt::layer()
+                    .with_writer(writer)
+                    .with_ansi(false);
+                (Some(layer), Some(guard), None)
+            }
+            Err(err) => (None, None, Some(format!("{err:#}"))),
+        };
+
+        let level = match verbosity {
+            0 => LevelFilter::INFO,
+            1 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        };
+        let mut diagnostics = Self {
+            events: Arc::default(),
+            log_dir,
+            log_error,
+        };
+        if let Err(err) = tracing_subscriber::registry()
+            .with(
+                Targets::new()
+                    .with_target(env!("CARGO_CRATE_NAME"), level)
+                    .with_default(LevelFilter::WARN),
+            )
+            .with(file_layer)
+            .with(diagnostics.clone())
+            .try_init()
+        {
+            diagnostics.log_error = Some(format!("cannot set up tracing: {err}"));
+        }
+        if let Some(err) = &diagnostics.log_error {
+            warn!("logging to files disabled: {err}");
+        }
+        (diagnostics, guard)
+    }
+
+    /// Returns the recent events, newest first.
+    pub fn events(&self) -> Vec<DiagnosticEvent> {
+        let events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        events.iter().rev().cloned().collect()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for Diagnostics {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        // trace events come every frame and would push everything else out,
+        // they only go to the log files
+        if *event.metadata().level() == Level::TRACE {
+            return;
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() == MAX_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(DiagnosticEvent {
+            at: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// Formats the message of an event followed by its other fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message.insert_str(0, &format!("{value:?}"));
+        } else {
+            let _ = write!(self.message, " {}={value:?}", field.name());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Export of the visible chart data and of tables to CSV or JSON files.
+
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{Local, TimeDelta};
+use eyre::Context as _;
+use serde::{Deserialize, Serialize};
+
+use crate::{network::Network, widgets::wallets_table::Wallet};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [Self; 2] = [Self::Csv, Self::Json];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Json => "JSON",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// The values of all charts of a network at the same x.
+#[derive(Serialize)]
+struct ChartPoint {
+    x: f64,
+    tps: f64,
+    node_cpu: f64,
+    node_net_up: f64,
+    node_net_down: f64,
+}
+
+/// Exports the points of the charts of `network` over the last `range`, or
+/// over the live window if unset, to the output `dir`, returns the path of the
+/// file and the number of points.
+pub fn charts(
+    network: &Network,
+    range: Option<TimeDelta>,
+    format: ExportFormat,
+    dir: &Path,
+) -> eyre::Result<(PathBuf, usize)> {
+    let ([start, end], [tps, cpu, up, down]) = network.charts(range);
+    let points = tps
+        .iter()
+        .zip(&cpu)
+        .zip(&up)
+        .zip(&down)
+        .map(|(((tps, cpu), up), down)| ChartPoint {
+            x: tps.0,
+            tps: tps.1,
+            node_cpu: cpu.1,
+            node_net_up: up.1,
+            node_net_down: down.1,
+        })
+        .filter(|point| (start..=end).contains(&point.x))
+        .collect::<Vec<_>>();
+    let path = path(dir, "charts", &network.name, format)?;
+    write(
+        &path,
+        format,
+        &["x", "tps", "node_cpu", "node_net_up", "node_net_down"],
+        &points,
+        |point| {
+            [
+                point.x,
+                point.tps,
+                point.node_cpu,
+                point.node_net_up,
+                point.node_net_down,
+            ]
+            .map(|value| value.to_string())
+            .to_vec()
+        },
+    )?;
+    Ok((path, points.len()))
+}
+
+/// Exports `wallets` of the network named `network` to the output `dir`,
+/// returns the path of the file and the number of wallets.
+pub fn wallets(
+    network: &str,
+    wallets: &[&Wallet],
+    format: ExportFormat,
+    dir: &Path,
+) -> eyre::Result<(PathBuf, usize)> {
+    let path = path(dir, "wallets", network, format)?;
+    write(
+        &path,
+        format,
+        &["address", "last_transfer", "transferred_amount", "balance"],
+        wallets,
+        |wallet| {
+            vec![
+                wallet.address.clone(),
+                wallet.last_transfer.clone(),
+                wallet.transferred_amount.to_string(),
+                wallet.balance.to_string(),
+            ]
+        },
+    )?;
+    Ok((path, wallets.len()))
+}
+
+/// Returns the path of the file `name` in the output `dir`, creating the
+/// directory first if needed.
+pub fn output_path(dir: &Path, name: &str) -> eyre::Result<PathBuf> {
+    fs::create_dir_all(dir).wrap_err_with(|| format!("cannot create {}", dir.display()))?;
+    Ok(dir.join(name))
+}
+
+/// Returns the timestamped path of an export of `what` of `network` in the
+/// output `dir`.
+fn path(dir: &Path, what: &str, network: &str, format: ExportFormat) -> eyre::Result<PathBuf> {
+    output_path(
+        dir,
+        &format!(
+            "merces-{what}-{}-{}.{}",
+            network.to_lowercase(),
+            Local::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ),
+    )
+}
+
+/// Writes `rows` to `path`, as a JSON array or as CSV with `header` and the
+/// `fields` of every row.
+fn write<T: Serialize>(
+    path: &Path,
+    format: ExportFormat,
+    header: &[&str],
+    rows: &[T],
+    fields: impl Fn(&T) -> Vec<String>,
+) -> eyre::Result<()> {
+    let file = File::create(path).wrap_err_with(|| format!("cannot create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    match format {
+        ExportFormat::Csv => {
+            writeln!(writer, "{}", header.join(","))?;
+            for row in rows {
+                let fields = fields(row)
+                    .iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<_>>();
+                writeln!(writer, "{}", fields.join(","))?;
+            }
+        }
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, rows)?;
+            writeln!(writer)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Quotes `field` if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_only_fields_that_need_it() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("0x1234...0001"), "0x1234...0001");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field("carriage\rreturn"), "\"carriage\rreturn\"");
+    }
+
+    #[test]
+    fn doubles_quotes_inside_quoted_fields() {
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("\""), "\"\"\"\"");
+        assert_eq!(csv_field("\"a\",\nb"), "\"\"\"a\"\",\nb\"");
+    }
+
+    #[test]
+    fn writes_csv_rows_into_the_output_dir() {
+        let dir = std::env::temp_dir().join(format!("merces-export-{}", std::process::id()));
+        let path = path(&dir, "test", "Base", ExportFormat::Csv).unwrap();
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        assert_eq!(path.extension().unwrap(), "csv");
+
+        let rows = [("a,b", 1), ("say \"hi\"", 2), ("two\nlines", 3)];
+        write(&path, ExportFormat::Csv, &["name", "n"], &rows, |row| {
+            vec![row.0.to_string(), row.1.to_string()]
+        })
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "name,n\n\"a,b\",1\n\"say \"\"hi\"\"\",2\n\"two\nlines\",3\n"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,223 @@
+//! Headless mode, printing snapshots of the metrics to stdout instead of
+//! drawing the UI.
+
+use std::{
+    io::{self, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use serde::Serialize;
+use tracing::info;
+
+use crate::{
+    TICK_RATE,
+    alerts::{AlertEngine, AlertStatus, Metric, sinks::AlertDispatcher},
+    config::Config,
+    load_networks,
+    network::Network,
+    source::SourceKind,
+    update,
+};
+
+/// How the snapshots are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One JSON object per network and snapshot
+    #[default]
+    Json,
+    /// A plain-text table with one row per network and snapshot
+    Text,
+}
+
+/// Runs the data pipeline of the UI and prints a snapshot of every network
+/// at a fixed interval.
+pub struct Headless {
+    networks: Vec<Network>,
+    alert_engine: AlertEngine,
+    alert_dispatcher: AlertDispatcher,
+    format: OutputFormat,
+    interval: Duration,
+    /// Number of snapshots printed before exiting, unlimited if not set
+    count: Option<u64>,
+}
+
+impl Headless {
+    pub fn new(
+        config: Config,
+        format: OutputFormat,
+        interval: Duration,
+        count: Option<u64>,
+    ) -> eyre::Result<Self> {
+        let networks = load_networks(config.networks, &config.history);
+        let network_names = networks
+            .iter()
+            .map(|network| network.name.clone())
+            .collect::<Vec<_>>();
+        info!(networks = networks.len(), ?format, "starting headless");
+        Ok(Self {
+            networks,
+            alert_engine: AlertEngine::new(config.alerts, &network_names)?,
+            alert_dispatcher: AlertDispatcher::new(config.alert_sinks),
+            format,
+            interval,
+            count,
+        })
+    }
+
+    /// Prints snapshots until the count is reached, or until stdout is closed.
+    pub fn run(mut self) -> eyre::Result<()> {
+        let mut stdout = io::stdout().lock();
+        let result = match self.print(&mut stdout) {
+            // the reader went away, e.g. `| head`
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result,
+        };
+        // deliver the alerts of the last snapshots before exiting
+        self.alert_dispatcher.shutdown();
+        result?;
+        info!("exiting");
+        Ok(())
+    }
+
+    fn print(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.format == OutputFormat::Text {
+            writeln!(out, "{}", text_header())?;
+        }
+        let mut printed = 0;
+        let mut last_snapshot = Instant::now();
+        while self.count.is_none_or(|count| printed < count) {
+            thread::sleep(TICK_RATE);
+            update(
+                &mut self.networks,
+                &mut self.alert_engine,
+                &self.alert_dispatcher,
+            );
+            if last_snapshot.elapsed() < self.interval {
+                continue;
+            }
+            last_snapshot = Instant::now();
+            let at = Local::now();
+            for network in &self.networks {
+                let snapshot = Snapshot::new(at, network, &self.alert_engine);
+                match self.format {
+                    OutputFormat::Json => {
+                        serde_json::to_writer(&mut *out, &snapshot)?;
+                        writeln!(out)?;
+                    }
+                    OutputFormat::Text => writeln!(out, "{}", snapshot.text_row())?,
+                }
+            }
+            out.flush()?;
+            printed += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Metrics and alert state of a network at one point in time, the metrics
+/// carry the names used by the alert rules.
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    at: DateTime<Local>,
+    network: &'a str,
+    tps: Option<f64>,
+    network_util: Option<f64>,
+    node_cpu: Option<f64>,
+    node_net_up: Option<f64>,
+    node_net_down: Option<f64>,
+    total_txs: Option<f64>,
+    avg_tx_latency: Option<f64>,
+    avg_proof_time: Option<f64>,
+    alerts: Vec<AlertSnapshot<'a>>,
+    /// Sources whose data is stale
+    stale: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct AlertSnapshot<'a> {
+    rule: &'a str,
+    status: AlertStatus,
+    value: Option<f64>,
+}
+
+impl<'a> Snapshot<'a> {
+    fn new(at: DateTime<Local>, network: &'a Network, alert_engine: &'a AlertEngine) -> Self {
+        Self {
+            at,
+            network: &network.name,
+            tps: network.metric(Metric::Tps),
+            network_util: network.metric(Metric::NetworkUtil),
+            node_cpu: network.metric(Metric::NodeCpu),
+            node_net_up: network.metric(Metric::NodeNetUp),
+            node_net_down: network.metric(Metric::NodeNetDown),
+            total_txs: network.metric(Metric::TotalTxs),
+            avg_tx_latency: network.metric(Metric::AvgTxLatency),
+            avg_proof_time: network.metric(Metric::AvgProofTime),
+            alerts: alert_engine
+                .rules
+                .iter()
+                .filter(|state| state.network == network.name)
+                .map(|state| AlertSnapshot {
+                    rule: &state.rule.name,
+                    status: state.status,
+                    value: state.value,
+                })
+                .collect(),
+            stale: SourceKind::ALL
+                .iter()
+                .map(|kind| network.source(*kind))
+                .filter(|source| source.is_stale())
+                .map(|source| source.kind.name())
+                .collect(),
+        }
+    }
+
+    fn text_row(&self) -> String {
+        let value = |value: Option<f64>, precision: usize| match value {
+            Some(value) => format!("{value:.precision$}"),
+            None => "-".to_string(),
+        };
+        let firing = self
+            .alerts
+            .iter()
+            .filter(|alert| alert.status == AlertStatus::Firing)
+            .map(|alert| alert.rule)
+            .collect::<Vec<_>>();
+        format!(
+            "{:<8}  {:<12}  {:>7}  {:>6}  {:>6}  {:>7}  {:>7}  {:>12}  {:>8}  {:>7}  {}",
+            self.at.format("%H:%M:%S"),
+            self.network,
+            value(self.tps, 2),
+            value(self.network_util, 1),
+            value(self.node_cpu, 1),
+            value(self.node_net_up, 2),
+            value(self.node_net_down, 2),
+            value(self.total_txs, 0),
+            value(self.avg_tx_latency, 1),
+            value(self.avg_proof_time, 2),
+            firing.join(", "),
+        )
+        .trim_end()
+        .to_string()
+    }
+}
+
+fn text_header() -> String {
+    format!(
+        "{:<8}  {:<12}  {:>7}  {:>6}  {:>6}  {:>7}  {:>7}  {:>12}  {:>8}  {:>7}  {}",
+        "time",
+        "network",
+        "tps",
+        "util%",
+        "cpu%",
+        "up",
+        "down",
+        "total txs",
+        "latency",
+        "proof",
+        "firing alerts",
+    )
+}
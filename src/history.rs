@@ -0,0 +1,368 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::Context as _;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tracing::warn;
+
+pub const TXS_PER_SECOND: &str = "txs_per_second";
+pub const NODE_CPU: &str = "node_cpu";
+pub const NODE_NET_UP: &str = "node_net_up";
+pub const NODE_NET_DOWN: &str = "node_net_down";
+pub const TX_LOGS: &str = "tx_logs";
+pub const MPC_LOGS: &str = "mpc_logs";
+pub const WALLETS: &str = "wallets";
+
+const EXTENSION: &str = "jsonl";
+/// Length of the time span a segment file covers.
+const SEGMENT_SECS: u64 = 60 * 60;
+const EXPIRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Serialize, Deserialize)]
+struct Record<T> {
+    ts: u64,
+    value: T,
+}
+
+/// Append-only on-disk store for time series, logs and wallet snapshots.
+///
+/// Every stream is split into hourly JSON lines segments named
+/// `<stream>.<segment>.jsonl` in `dir`, so that loading only reads the newest
+/// segments and expired records are dropped by deleting whole files.
+pub struct HistoryStore {
+    dir: PathBuf,
+    retention: Duration,
+    /// Open segment of every stream written to
+    writers: HashMap<&'static str, (u64, BufWriter<File>)>,
+    last_expiry: Instant,
+}
+
+impl HistoryStore {
+    pub fn open(dir: impl Into<PathBuf>, retention: Duration) -> eyre::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .wrap_err_with(|| format!("cannot create history dir {}", dir.display()))?;
+        let mut store = Self {
+            dir,
+            retention,
+            writers: HashMap::new(),
+            last_expiry: Instant::now(),
+        };
+        store.expire();
+        Ok(store)
+    }
+
+    /// Loads the last `max` records of `stream` within the retention, oldest
+    /// first, reading only as many segments as needed.
+    pub fn load_tail<T: DeserializeOwned>(&self, stream: &str, max: usize) -> eyre::Result<Vec<T>> {
+        let cutoff = self.cutoff();
+        let mut segments = self.segments(stream)?;
+        segments.sort_unstable_by_key(|(segment, _)| Reverse(*segment));
+        let mut lines = Vec::new();
+        for (_, path) in segments {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                // deleted by the expiry running in the background
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(err).wrap_err_with(|| format!("cannot open {}", path.display()));
+                }
+            };
+            let mut segment = BufReader::new(file)
+                .lines()
+                .collect::<Result<Vec<_>, _>>()?;
+            segment.append(&mut lines);
+            lines = segment;
+            if lines.len() >= max {
+                break;
+            }
+        }
+        let skip = lines.len().saturating_sub(max);
+        Ok(lines
+            .iter()
+            .skip(skip)
+            // a torn last line after a crash is skipped like any malformed record
+            .filter_map(|line| serde_json::from_str::<Record<T>>(line).ok())
+            .filter(|record| record.ts >= cutoff)
+            .map(|record| record.value)
+            .collect())
+    }
+
+    pub fn append<T: Serialize>(
+        &mut self,
+        stream: &'static str,
+        values: impl IntoIterator<Item = T>,
+    ) -> eyre::Result<()> {
+        let ts = now();
+        let segment = ts / SEGMENT_SECS;
+        if self
+            .writers
+            .get(stream)
+            .is_none_or(|(open, _)| *open != segment)
+        {
+            if let Some((_, mut writer)) = self.writers.remove(stream) {
+                writer.flush()?;
+            }
+            let path = self.path(stream, segment);
+            let mut file = OpenOptions::new()
+                .create(true)
+                .read(true)
+                .append(true)
+                .open(&path)
+                .wrap_err_with(|| format!("cannot open {}", path.display()))?;
+            // end a line torn by a crash, so that it does not swallow the next record
+            if ends_torn(&mut file)? {
+                file.write_all(b"\n")?;
+            }
+            self.writers.insert(stream, (segment, BufWriter::new(file)));
+        }
+        let (_, writer) = self
+            .writers
+            .get_mut(stream)
+            .expect("writer was just inserted");
+        for value in values {
+            serde_json::to_writer(&mut *writer, &Record { ts, value })?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Flushes pending records and deletes expired segments if it is due.
+    pub fn on_tick(&mut self) -> eyre::Result<()> {
+        for (_, writer) in self.writers.values_mut() {
+            writer.flush()?;
+        }
+        if self.last_expiry.elapsed() >= EXPIRY_INTERVAL {
+            self.expire();
+        }
+        Ok(())
+    }
+
+    /// Deletes the segments that only hold records older than the retention,
+    /// in the background.
+    fn expire(&mut self) {
+        let dir = self.dir.clone();
+        let cutoff = self.cutoff();
+        thread::spawn(move || {
+            if let Err(err) = remove_expired(&dir, cutoff) {
+                warn!(dir = %dir.display(), "cannot delete expired history: {err:#}");
+            }
+        });
+        self.last_expiry = Instant::now();
+    }
+
+    /// Returns the segment numbers and paths of `stream`.
+    fn segments(&self, stream: &str) -> eyre::Result<Vec<(u64, PathBuf)>> {
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if let Some((name, segment)) = parse_segment(&path)
+                && name == stream
+            {
+                segments.push((segment, path));
+            }
+        }
+        Ok(segments)
+    }
+
+    fn path(&self, stream: &str, segment: u64) -> PathBuf {
+        self.dir.join(format!("{stream}.{segment}.{EXTENSION}"))
+    }
+
+    fn cutoff(&self) -> u64 {
+        now().saturating_sub(self.retention.as_secs())
+    }
+}
+
+/// Returns the name of the history directory of `network`, a slug of the name
+/// that stays inside the history root, followed by a hash of the exact name so
+/// that names differing only in case or punctuation get their own directory.
+pub fn dir_name(network: &str) -> String {
+    let slug = network
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    // FNV-1a, unlike the std hashers it is stable across releases
+    let hash = network
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{slug}-{:08x}", hash as u32)
+}
+
+/// Returns whether `file` ends in the middle of a line.
+fn ends_torn(file: &mut File) -> io::Result<bool> {
+    let len = file.metadata()?.len();
+    if len == 0 {
+        return Ok(false);
+    }
+    file.seek(SeekFrom::Start(len - 1))?;
+    let mut last = [0];
+    file.read_exact(&mut last)?;
+    Ok(last[0] != b'\n')
+}
+
+/// Returns the stream name and segment number of a segment file.
+fn parse_segment(path: &Path) -> Option<(&str, u64)> {
+    let name = path.file_name()?.to_str()?.strip_suffix(EXTENSION)?;
+    let (stream, segment) = name.strip_suffix('.')?.rsplit_once('.')?;
+    Some((stream, segment.parse().ok()?))
+}
+
+fn remove_expired(dir: &Path, cutoff: u64) -> eyre::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some((_, segment)) = parse_segment(&path)
+            && (segment + 1) * SEGMENT_SECS <= cutoff
+        {
+            fs::remove_file(&path).wrap_err_with(|| format!("cannot delete {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns an empty directory for the test `name`.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("merces-history-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_segment(dir: &Path, stream: &str, segment: u64, records: &[(u64, u32)]) {
+        let lines = records
+            .iter()
+            .map(|(ts, value)| format!("{{\"ts\":{ts},\"value\":{value}}}\n"))
+            .collect::<String>();
+        fs::write(dir.join(format!("{stream}.{segment}.{EXTENSION}")), lines).unwrap();
+    }
+
+    #[test]
+    fn loads_the_tail_of_what_was_appended() {
+        let dir = test_dir("append");
+        let mut store = HistoryStore::open(&dir, Duration::from_secs(3600)).unwrap();
+        store.append(NODE_CPU, [1, 2, 3, 4, 5]).unwrap();
+        store.append(NODE_NET_UP, [9]).unwrap();
+        store.on_tick().unwrap();
+
+        assert_eq!(store.load_tail::<u32>(NODE_CPU, 3).unwrap(), [3, 4, 5]);
+        assert_eq!(
+            store.load_tail::<u32>(NODE_CPU, 10).unwrap(),
+            [1, 2, 3, 4, 5]
+        );
+        assert_eq!(store.load_tail::<u32>(NODE_NET_UP, 10).unwrap(), [9]);
+        assert!(store.load_tail::<u32>(WALLETS, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn loads_the_tail_across_segments_within_the_retention() {
+        let dir = test_dir("segments");
+        let now = now();
+        let segment = now / SEGMENT_SECS;
+        write_segment(&dir, NODE_CPU, segment - 2, &[(now - 2 * SEGMENT_SECS, 1)]);
+        write_segment(&dir, NODE_CPU, segment - 1, &[(now - 60, 2), (now - 30, 3)]);
+        write_segment(&dir, NODE_CPU, segment, &[(now, 4)]);
+        let store = HistoryStore::open(&dir, Duration::from_secs(SEGMENT_SECS)).unwrap();
+
+        assert_eq!(store.load_tail::<u32>(NODE_CPU, 2).unwrap(), [3, 4]);
+        // the record of the oldest segment is past the retention
+        assert_eq!(store.load_tail::<u32>(NODE_CPU, 10).unwrap(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn skips_a_torn_line_and_appends_after_it() {
+        let dir = test_dir("torn");
+        let now = now();
+        let path = dir.join(format!("{NODE_CPU}.{}.{EXTENSION}", now / SEGMENT_SECS));
+        fs::write(
+            &path,
+            format!("{{\"ts\":{now},\"value\":1}}\n{{\"ts\":{now},\"va"),
+        )
+        .unwrap();
+        let mut store = HistoryStore::open(&dir, Duration::from_secs(3600)).unwrap();
+        assert_eq!(store.load_tail::<u32>(NODE_CPU, 10).unwrap(), [1]);
+
+        store.append(NODE_CPU, [2]).unwrap();
+        store.on_tick().unwrap();
+        assert_eq!(store.load_tail::<u32>(NODE_CPU, 10).unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn parses_segment_file_names() {
+        fn parse(name: &str) -> Option<(&str, u64)> {
+            parse_segment(Path::new(name))
+        }
+        assert_eq!(parse("tx_logs.42.jsonl"), Some(("tx_logs", 42)));
+        assert_eq!(
+            parse("/tmp/history/node_cpu.0.jsonl"),
+            Some(("node_cpu", 0))
+        );
+        assert_eq!(parse("tx_logs.jsonl"), None);
+        assert_eq!(parse("tx_logs.latest.jsonl"), None);
+        assert_eq!(parse("tx_logs.42.json"), None);
+        assert_eq!(parse("tx_logs.42.jsonl.tmp"), None);
+    }
+
+    #[test]
+    fn removes_only_expired_segments() {
+        let dir = test_dir("expire");
+        let now = now();
+        let segment = now / SEGMENT_SECS;
+        write_segment(&dir, NODE_CPU, segment - 3, &[]);
+        write_segment(&dir, NODE_CPU, segment - 1, &[]);
+        write_segment(&dir, TX_LOGS, segment, &[]);
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        remove_expired(&dir, (segment - 1) * SEGMENT_SECS + 1).unwrap();
+        let mut left = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        left.sort();
+        assert_eq!(
+            left,
+            [
+                format!("{NODE_CPU}.{}.{EXTENSION}", segment - 1),
+                "notes.txt".to_string(),
+                format!("{TX_LOGS}.{segment}.{EXTENSION}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn dir_names_stay_inside_the_root_and_are_distinct() {
+        assert_ne!(dir_name("Base"), dir_name("base"));
+        assert!(dir_name("Base").starts_with("base-"));
+        for name in ["../x", "a/b", "..", "/etc", "C:\\x"] {
+            let dir = dir_name(name);
+            assert_eq!(Path::new(&dir).components().count(), 1, "{name} -> {dir}");
+            assert!(!dir.contains(['/', '\\', '.']), "{name} -> {dir}");
+        }
+    }
+}
@@ -0,0 +1,737 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer};
+
+/// Something the user can trigger with a key binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    ToggleMenu,
+    NextPage,
+    PreviousPage,
+    NextNetwork,
+    NextTheme,
+    Screenshot,
+    ScrollDown,
+    ScrollUp,
+    Top,
+    Bottom,
+    FocusSearch,
+    CycleFocus,
+    Unfocus,
+    Submit,
+    TogglePause,
+    ToggleWrap,
+    JumpToTime,
+    Export,
+    ShowHelp,
+    OpenPalette,
+}
+
+impl Action {
+    /// Short label used in the hint footer.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::ToggleMenu => "menu",
+            Self::NextPage => "next page",
+            Self::PreviousPage => "previous page",
+            Self::NextNetwork => "network",
+            Self::NextTheme => "theme",
+            Self::Screenshot => "screenshot",
+            Self::ScrollDown => "down",
+            Self::ScrollUp => "up",
+            Self::Top => "oldest",
+            Self::Bottom => "newest",
+            Self::FocusSearch => "search",
+            Self::CycleFocus => "focus logs",
+            Self::Unfocus => "back",
+            Self::Submit => "submit",
+            Self::TogglePause => "pause",
+            Self::ToggleWrap => "wrap",
+            Self::JumpToTime => "jump to time",
+            Self::Export => "export",
+            Self::ShowHelp => "help",
+            Self::OpenPalette => "commands",
+        }
+    }
+
+    /// Description used in the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::ToggleMenu => "Show or hide the menu",
+            Self::NextPage => "Go to the next page",
+            Self::PreviousPage => "Go to the previous page",
+            Self::NextNetwork => "Switch to the next network",
+            Self::NextTheme => "Switch to the next theme",
+            Self::Screenshot => "Save a screenshot of the page",
+            Self::ScrollDown => "Scroll down",
+            Self::ScrollUp => "Scroll up",
+            Self::Top => "Jump to the oldest entry",
+            Self::Bottom => "Jump to the newest entry",
+            Self::FocusSearch => "Search or filter",
+            Self::CycleFocus => "Cycle focus between the log panels",
+            Self::Unfocus => "Leave the focused input or panel",
+            Self::Submit => "Submit the input",
+            Self::TogglePause => "Pause or resume live updates",
+            Self::ToggleWrap => "Wrap or truncate long messages",
+            Self::JumpToTime => "Jump to a time",
+            Self::Export => "Export to a file",
+            Self::ShowHelp => "Show this help",
+            Self::OpenPalette => "Open the command palette",
+        }
+    }
+}
+
+/// Where the keyboard focus is, selects the bindings that apply.
+///
+/// Keys not bound in the table context fall back to the global bindings, keys
+/// not bound in the search context are typed into the focused input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Context {
+    Global,
+    Table,
+    Search,
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => write!(f, "Global"),
+            Self::Table => write!(f, "Table focused"),
+            Self::Search => write!(f, "Search focused"),
+        }
+    }
+}
+
+/// A single key press with its modifiers, written like `ctrl-d`, `G` or `esc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for Key {
+    fn from(key: KeyEvent) -> Self {
+        let mut modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers |= key.modifiers & KeyModifiers::SHIFT;
+        }
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        while let Some((modifier, key)) = rest.split_once('-')
+            && !key.is_empty()
+        {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier {modifier:?} in key {s:?}")),
+            };
+            rest = key;
+        }
+        let code = match rest.to_lowercase().as_str() {
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers -= KeyModifiers::SHIFT;
+                KeyCode::BackTab
+            }
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            key => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                        Some(n) => KeyCode::F(n),
+                        None => return Err(format!("unknown key {s:?}")),
+                    },
+                }
+            }
+        };
+        Ok(Self::from(KeyEvent::new(code, modifiers)))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            KeyCode::BackTab => write!(f, "shift-tab"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            code => write!(f, "{}", code.to_string().to_lowercase()),
+        }
+    }
+}
+
+/// A sequence of keys pressed one after another, written space separated like `g g`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(pub Vec<Key>);
+
+impl FromStr for KeySequence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+impl KeySequence {
+    /// Returns whether the sequences cannot be bound side by side, because
+    /// they are equal or one is the start of the other.
+    fn conflicts(&self, other: &KeySequence) -> bool {
+        self.0.starts_with(&other.0) || other.0.starts_with(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeySequence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Key bindings from the `[keymap.<context>]` tables of the config, each
+/// listed action replaces all default bindings of that action in the context
+/// and the keys it is bound to are taken from the default bindings of other
+/// actions there.
+pub type KeymapConfig = BTreeMap<Context, BTreeMap<Action, Vec<KeySequence>>>;
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub context: Context,
+    pub keys: KeySequence,
+    pub action: Action,
+}
+
+/// Outcome of feeding a key press to the [`Keymap`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum Resolved {
+    /// The actions bound to the keys, most specific context first
+    Actions(Vec<Action>),
+    /// The keys so far are the start of a longer sequence
+    Pending,
+    /// The keys pressed since the last resolved action are not bound
+    Unbound(Vec<KeyEvent>),
+}
+
+/// How long the keymap waits for the next key of a sequence before the keys
+/// pressed so far are resolved on their own.
+pub const SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
+const DEFAULT_BINDINGS: &[(Context, Action, &[&str])] = &[
+    (Context::Global, Action::Quit, &["q", "esc"]),
+    (Context::Global, Action::ToggleMenu, &["m"]),
+    (Context::Global, Action::NextPage, &["tab"]),
+    (Context::Global, Action::PreviousPage, &["shift-tab"]),
+    (Context::Global, Action::NextNetwork, &["n"]),
+    (Context::Global, Action::NextTheme, &["T"]),
+    (Context::Global, Action::Screenshot, &["S"]),
+    (
+        Context::Global,
+        Action::ScrollDown,
+        &["j", "down", "pagedown", "ctrl-d"],
+    ),
+    (
+        Context::Global,
+        Action::ScrollUp,
+        &["k", "up", "pageup", "ctrl-u"],
+    ),
+    (Context::Global, Action::FocusSearch, &["s", "f"]),
+    (Context::Global, Action::CycleFocus, &["l"]),
+    (Context::Global, Action::Export, &["e"]),
+    (Context::Global, Action::ShowHelp, &["?"]),
+    (Context::Global, Action::OpenPalette, &[":", "ctrl-p"]),
+    (Context::Table, Action::ScrollDown, &["j", "down"]),
+    (Context::Table, Action::ScrollUp, &["k", "up"]),
+    (Context::Table, Action::Top, &["g", "home"]),
+    (Context::Table, Action::Bottom, &["G", "end"]),
+    (Context::Table, Action::FocusSearch, &["/"]),
+    (Context::Table, Action::TogglePause, &["space", "p"]),
+    (Context::Table, Action::ToggleWrap, &["w"]),
+    (Context::Table, Action::JumpToTime, &["t"]),
+    (Context::Table, Action::Export, &["e"]),
+    (Context::Table, Action::Unfocus, &["esc"]),
+    (
+        Context::Search,
+        Action::Unfocus,
+        &["esc", "tab", "shift-tab"],
+    ),
+    (Context::Search, Action::Submit, &["enter"]),
+    (Context::Search, Action::ScrollDown, &["down", "ctrl-n"]),
+    (Context::Search, Action::ScrollUp, &["up", "ctrl-p"]),
+];
+
+/// Maps key sequences to actions per [`Context`].
+pub struct Keymap {
+    bindings: Vec<Binding>,
+    pending: Vec<KeyEvent>,
+    /// When the last key of the pending sequence was pressed
+    pending_since: Option<Instant>,
+}
+
+impl Keymap {
+    /// Creates the default bindings overridden by `config`.
+    pub fn new(config: KeymapConfig) -> Self {
+        let configured = config
+            .into_iter()
+            .flat_map(|(context, actions)| {
+                actions.into_iter().flat_map(move |(action, sequences)| {
+                    sequences.into_iter().map(move |keys| Binding {
+                        context,
+                        keys,
+                        action,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut bindings = Vec::new();
+        for &(context, action, keys) in DEFAULT_BINDINGS {
+            let replaced = |keys: &KeySequence| {
+                configured.iter().any(|binding| {
+                    binding.context == context
+                        && (binding.action == action || binding.keys.conflicts(keys))
+                })
+            };
+            bindings.extend(
+                keys.iter()
+                    .map(|keys| keys.parse().expect("default key bindings are valid"))
+                    .filter(|keys| !replaced(keys))
+                    .map(|keys| Binding {
+                        context,
+                        keys,
+                        action,
+                    }),
+            );
+        }
+        bindings.extend(configured);
+        Self {
+            bindings,
+            pending: Vec::new(),
+            pending_since: None,
+        }
+    }
+
+    /// All bindings that apply in `context`, in the order they are looked up.
+    pub fn bindings(&self, context: Context) -> impl Iterator<Item = &Binding> {
+        let fallback = match context {
+            Context::Table => Some(Context::Global),
+            Context::Global | Context::Search => None,
+        };
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.context == context)
+            .chain(
+                self.bindings
+                    .iter()
+                    .filter(move |binding| Some(binding.context) == fallback),
+            )
+    }
+
+    /// Returns the key sequences that trigger each of `actions` in `context`.
+    ///
+    /// Sequences shadowed by a binding of the same keys in the more specific
+    /// context are left out, as are actions without any key sequence.
+    pub fn help(&self, context: Context, actions: &[Action]) -> Vec<(Action, Vec<&KeySequence>)> {
+        let mut seen = Vec::new();
+        let mut help = actions
+            .iter()
+            .map(|action| (*action, Vec::new()))
+            .collect::<Vec<_>>();
+        for binding in self.bindings(context) {
+            if seen.contains(&&binding.keys) {
+                continue;
+            }
+            if let Some((_, keys)) = help
+                .iter_mut()
+                .find(|(action, _)| *action == binding.action)
+            {
+                keys.push(&binding.keys);
+                seen.push(&binding.keys);
+            }
+        }
+        help.retain(|(_, keys)| !keys.is_empty());
+        help
+    }
+
+    /// Feeds `key` to the current key sequence, returns the outcomes in the
+    /// order they are to be handled.
+    ///
+    /// Keys that are bound on their own and as the start of a longer sequence
+    /// wait for the next key or for the [`SEQUENCE_TIMEOUT`]. If the next key
+    /// breaks the sequence, the keys before it are resolved on their own and
+    /// it starts a new sequence.
+    pub fn resolve(&mut self, context: Context, key: KeyEvent) -> Vec<Resolved> {
+        self.pending.push(key);
+        let (actions, longer) = self.lookup(context, &self.pending);
+        if longer {
+            self.pending_since = Some(Instant::now());
+            vec![Resolved::Pending]
+        } else if !actions.is_empty() {
+            self.pending.clear();
+            vec![Resolved::Actions(actions)]
+        } else if self.pending.len() == 1 {
+            vec![Resolved::Unbound(std::mem::take(&mut self.pending))]
+        } else {
+            self.pending.pop();
+            let mut resolved = vec![self.flush(context)];
+            resolved.extend(self.resolve(context, key));
+            resolved
+        }
+    }
+
+    /// Resolves the pending keys on their own once no further key was pressed
+    /// within the [`SEQUENCE_TIMEOUT`].
+    pub fn resolve_timeout(&mut self, context: Context) -> Option<Resolved> {
+        if self.pending.is_empty()
+            || self
+                .pending_since
+                .is_none_or(|since| since.elapsed() < SEQUENCE_TIMEOUT)
+        {
+            return None;
+        }
+        Some(self.flush(context))
+    }
+
+    /// Resolves the pending keys as if the sequence ended after them.
+    fn flush(&mut self, context: Context) -> Resolved {
+        let keys = std::mem::take(&mut self.pending);
+        self.pending_since = None;
+        let (actions, _) = self.lookup(context, &keys);
+        if actions.is_empty() {
+            Resolved::Unbound(keys)
+        } else {
+            Resolved::Actions(actions)
+        }
+    }
+
+    /// Returns the actions bound to exactly `pressed` in `context` and whether
+    /// a longer sequence starts with it.
+    fn lookup(&self, context: Context, pressed: &[KeyEvent]) -> (Vec<Action>, bool) {
+        let pressed = pressed
+            .iter()
+            .map(|key| Key::from(*key))
+            .collect::<Vec<_>>();
+        let mut actions = Vec::new();
+        let mut longer = false;
+        for binding in self.bindings(context) {
+            let keys = &binding.keys.0;
+            if keys.starts_with(&pressed) {
+                if keys.len() == pressed.len() {
+                    actions.push(binding.action);
+                } else {
+                    longer = true;
+                }
+            }
+        }
+        (actions, longer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> KeyEvent {
+        let key = s.parse::<Key>().unwrap();
+        KeyEvent::new(key.code, key.modifiers)
+    }
+
+    fn keymap(bindings: &[(Context, Action, &str)]) -> Keymap {
+        Keymap {
+            bindings: bindings
+                .iter()
+                .map(|&(context, action, keys)| Binding {
+                    context,
+                    keys: keys.parse().unwrap(),
+                    action,
+                })
+                .collect(),
+            pending: Vec::new(),
+            pending_since: None,
+        }
+    }
+
+    #[test]
+    fn parses_keys() {
+        let parsed = |s: &str| s.parse::<Key>().unwrap();
+        assert_eq!(
+            parsed("ctrl-d"),
+            Key::from(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parsed("G"),
+            Key::from(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE))
+        );
+        assert_eq!(parsed("shift-g"), parsed("G"));
+        assert_eq!(
+            parsed("shift-tab"),
+            Key::from(KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parsed("ESC"),
+            Key::from(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parsed("f5"),
+            Key::from(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parsed("ctrl-alt-space"),
+            Key::from(KeyEvent::new(
+                KeyCode::Char(' '),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(
+            parsed("-"),
+            Key::from(KeyEvent::new(KeyCode::Char('-'), KeyModifiers::NONE))
+        );
+        assert!("hyper-x".parse::<Key>().is_err());
+        assert!("foo".parse::<Key>().is_err());
+        assert!("".parse::<KeySequence>().is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in [
+            "q",
+            "G",
+            "ctrl-d",
+            "alt-x",
+            "ctrl-alt-space",
+            "shift-tab",
+            "esc",
+            "enter",
+            "pageup",
+            "pagedown",
+            "shift-up",
+            "f12",
+            "-",
+        ] {
+            let key = s.parse::<Key>().unwrap();
+            assert_eq!(key.to_string(), s);
+            assert_eq!(key.to_string().parse::<Key>().unwrap(), key);
+        }
+        let sequence = "g ctrl-g G".parse::<KeySequence>().unwrap();
+        assert_eq!(sequence.to_string(), "g ctrl-g G");
+    }
+
+    #[test]
+    fn specific_context_shadows_global() {
+        let mut keymap = keymap(&[
+            (Context::Global, Action::Quit, "q"),
+            (Context::Global, Action::Quit, "esc"),
+            (Context::Table, Action::Unfocus, "esc"),
+        ]);
+        assert_eq!(
+            keymap.resolve(Context::Table, key("esc")),
+            [Resolved::Actions(vec![Action::Unfocus, Action::Quit])]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Global, key("esc")),
+            [Resolved::Actions(vec![Action::Quit])]
+        );
+        let help = keymap.help(Context::Table, &[Action::Quit, Action::Unfocus]);
+        let help = help
+            .iter()
+            .map(|(action, keys)| (*action, keys.iter().map(ToString::to_string).collect()))
+            .collect::<Vec<(Action, Vec<String>)>>();
+        assert_eq!(
+            help,
+            [
+                (Action::Quit, vec!["q".to_string()]),
+                (Action::Unfocus, vec!["esc".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn configured_keys_replace_the_defaults_bound_to_them() {
+        let config = KeymapConfig::from([
+            (
+                Context::Global,
+                BTreeMap::from([(Action::Screenshot, vec!["q".parse().unwrap()])]),
+            ),
+            (
+                Context::Table,
+                BTreeMap::from([(Action::TogglePause, vec!["g x".parse().unwrap()])]),
+            ),
+        ]);
+        let mut keymap = Keymap::new(config);
+        assert_eq!(
+            keymap.resolve(Context::Global, key("q")),
+            [Resolved::Actions(vec![Action::Screenshot])]
+        );
+        // the other defaults of the action stay bound
+        assert_eq!(
+            keymap.resolve(Context::Global, key("esc")),
+            [Resolved::Actions(vec![Action::Quit])]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Global, key("S")),
+            [Resolved::Unbound(vec![key("S")])]
+        );
+        // a default that is the start of a configured sequence is dropped too
+        assert_eq!(
+            keymap.resolve(Context::Table, key("g")),
+            [Resolved::Pending]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("x")),
+            [Resolved::Actions(vec![Action::TogglePause])]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("home")),
+            [Resolved::Actions(vec![Action::Top])]
+        );
+    }
+
+    #[test]
+    fn resolves_sequences() {
+        let mut keymap = keymap(&[
+            (Context::Table, Action::Top, "g g"),
+            (Context::Table, Action::Bottom, "G"),
+        ]);
+        assert_eq!(
+            keymap.resolve(Context::Table, key("g")),
+            [Resolved::Pending]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("g")),
+            [Resolved::Actions(vec![Action::Top])]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("x")),
+            [Resolved::Unbound(vec![key("x")])]
+        );
+        assert_eq!(keymap.resolve_timeout(Context::Table), None);
+    }
+
+    #[test]
+    fn broken_sequence_replays_the_last_key() {
+        let mut keymap = keymap(&[
+            (Context::Table, Action::Top, "g"),
+            (Context::Table, Action::Export, "g e"),
+            (Context::Table, Action::Bottom, "G"),
+        ]);
+        assert_eq!(
+            keymap.resolve(Context::Table, key("g")),
+            [Resolved::Pending]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("G")),
+            [
+                Resolved::Actions(vec![Action::Top]),
+                Resolved::Actions(vec![Action::Bottom]),
+            ]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("g")),
+            [Resolved::Pending]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("g")),
+            [Resolved::Actions(vec![Action::Top]), Resolved::Pending]
+        );
+        assert_eq!(
+            keymap.resolve(Context::Table, key("e")),
+            [Resolved::Actions(vec![Action::Export])]
+        );
+    }
+
+    #[test]
+    fn timeout_fires_the_exact_match() {
+        let mut keymap = keymap(&[
+            (Context::Table, Action::Top, "g"),
+            (Context::Table, Action::Export, "g e"),
+            (Context::Table, Action::JumpToTime, "t t"),
+        ]);
+        assert_eq!(
+            keymap.resolve(Context::Table, key("g")),
+            [Resolved::Pending]
+        );
+        assert_eq!(keymap.resolve_timeout(Context::Table), None);
+        keymap.pending_since = Some(Instant::now() - SEQUENCE_TIMEOUT);
+        assert_eq!(
+            keymap.resolve_timeout(Context::Table),
+            Some(Resolved::Actions(vec![Action::Top]))
+        );
+        assert_eq!(keymap.resolve_timeout(Context::Table), None);
+
+        // a prefix without an action of its own ends up unbound
+        keymap.resolve(Context::Table, key("t"));
+        keymap.pending_since = Some(Instant::now() - SEQUENCE_TIMEOUT);
+        assert_eq!(
+            keymap.resolve_timeout(Context::Table),
+            Some(Resolved::Unbound(vec![key("t")]))
+        );
+    }
+}
@@ -1,95 +1,334 @@
-use std::time::{Duration, Instant};
+use std::{
+    mem,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use ratatui::{
     DefaultTerminal, Frame,
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
-    layout::{Constraint, Layout, Offset, Rect, Size},
-    style::{Color, Style},
-    text::Line,
+    crossterm::event::{
+        self, Event, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+    },
+    layout::{Constraint, Layout, Margin, Offset, Position, Rect, Size},
+    style::Style,
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListState, Paragraph, StatefulWidget, Widget},
 };
-use ratatui_image::{Resize, picker::Picker};
 
-use crate::pages::{dashboard::Dashboard, intro::Intro, wallets::Wallets};
+use tracing::{info, trace, warn};
+
+use crate::{
+    alerts::{AlertEngine, AlertEvent, sinks::AlertDispatcher},
+    config::{Config, HistoryConfig},
+    diagnostics::Diagnostics,
+    export::ExportFormat,
+    keymap::{Action, Context, Keymap, Resolved},
+    network::{Network, NetworkConfig},
+    pages::Page,
+    recording::Recorder,
+    screenshot::ScreenshotFormat,
+    theme::Theme,
+    widgets::{
+        command_palette::{Command, CommandKind, CommandPalette, CommandPaletteState},
+        help::{Help, KeyHints},
+        logo::Logo,
+        status_bar::StatusBar,
+        toasts::Toasts,
+    },
+};
 
+mod alerts;
+pub mod config;
+pub mod crash;
+pub mod diagnostics;
+mod export;
+pub mod headless;
+mod history;
+mod keymap;
 mod layout;
+mod logs;
+mod network;
 mod pages;
+mod recording;
+mod screenshot;
+mod source;
+mod theme;
 mod widgets;
 
 const LARGE_MIN: u16 = 120;
 
-const ACCENT_COLOR: Color = Color::Indexed(122);
+/// Interval at which the networks are updated.
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Log time ranges offered by the command palette.
+const TIME_RANGES: [(&str, chrono::TimeDelta); 4] = [
+    ("1 minute", chrono::TimeDelta::minutes(1)),
+    ("5 minutes", chrono::TimeDelta::minutes(5)),
+    ("15 minutes", chrono::TimeDelta::minutes(15)),
+    ("1 hour", chrono::TimeDelta::hours(1)),
+];
+
+const TOAST_DURATION: chrono::TimeDelta = chrono::TimeDelta::seconds(5);
+
+/// Time the outcome of a screenshot or an export replaces the key hints.
+const NOTICE_DURATION: Duration = Duration::from_secs(5);
+
+/// Frames taking longer than this to render are logged as warnings.
+const SLOW_RENDER: Duration = Duration::from_millis(50);
 
-const SELECTED_STYLE: ratatui::style::Style = ratatui::style::Style::new()
-    .bg(ACCENT_COLOR)
-    .fg(Color::Black);
+/// Data shared by all pages.
+pub struct AppState {
+    networks: Vec<Network>,
+    /// Index of the selected network
+    network: usize,
+    alert_engine: AlertEngine,
+    themes: Vec<Theme>,
+    /// Index of the selected theme
+    theme: usize,
+    diagnostics: Diagnostics,
+    /// Format of the data exported with the export key
+    export_format: ExportFormat,
+    /// Directory screenshots and exports are written to
+    output_dir: PathBuf,
+}
+
+impl AppState {
+    /// Returns the selected network.
+    fn network(&self) -> &Network {
+        &self.networks[self.network]
+    }
+
+    /// Returns the selected theme.
+    fn theme(&self) -> &Theme {
+        &self.themes[self.theme]
+    }
+}
 
 pub struct App {
     menu_state: ListState,
     show_menu: bool,
     should_exit: bool,
-    image: ratatui_image::protocol::Protocol,
-    intro: Intro,
-    dashboard: Dashboard,
-    wallets: Wallets,
+    logo: Logo,
+    pages: Vec<Box<dyn Page>>,
+    state: AppState,
+    alert_dispatcher: AlertDispatcher,
+    toasts: Vec<AlertEvent>,
+    keymap: Keymap,
+    show_help: bool,
+    palette: Option<CommandPaletteState>,
+    /// Screen area of the menu list, used to map clicks to menu items
+    menu_area: Rect,
+    screenshot_format: ScreenshotFormat,
+    /// Format of the screenshot taken with the next frame
+    screenshot: Option<ScreenshotFormat>,
+    /// Outcome of the last screenshot or export shown in the footer, and when
+    /// it happened
+    notice: Option<(Result<String, String>, Instant)>,
+    /// Recording of the frames, if enabled
+    recorder: Option<Recorder>,
 }
 
 impl App {
-    pub fn new() -> eyre::Result<Self> {
+    pub fn new(config: Config, diagnostics: Diagnostics) -> eyre::Result<Self> {
         let mut menu_state = ListState::default();
         menu_state.select(Some(1));
 
-        let picker = Picker::from_query_stdio()?;
-        let dyn_img = image::ImageReader::open("logo.png")?.decode()?;
-        let image = picker.new_protocol(dyn_img, Rect::new(0, 0, 4, 4), Resize::Scale(None))?;
+        let output_dir = config.output_dir();
+        let networks = load_networks(config.networks, &config.history);
+        let network_names = networks
+            .iter()
+            .map(|network| network.name.clone())
+            .collect::<Vec<_>>();
+        let themes = Theme::all(config.themes);
+        // without colors the monochrome theme is used regardless of the config
+        let theme_name = if theme::colors_disabled() {
+            Some("monochrome")
+        } else {
+            config.theme.as_deref()
+        };
+        let theme = match theme_name {
+            Some(name) => themes
+                .iter()
+                .position(|theme| theme.name == name)
+                .ok_or_else(|| eyre::eyre!("unknown theme `{name}`"))?,
+            None => 0,
+        };
+        info!(
+            networks = networks.len(),
+            theme = %themes[theme].name,
+            "starting"
+        );
 
         Ok(Self {
             menu_state,
             show_menu: true,
             should_exit: false,
-            image,
-            intro: Intro::new(),
-            dashboard: Dashboard::new(),
-            wallets: Wallets::new(),
+            logo: Logo::new(config.logo, !theme::colors_disabled()),
+            pages: pages::pages(),
+            state: AppState {
+                networks,
+                network: 0,
+                alert_engine: AlertEngine::new(config.alerts, &network_names)?,
+                themes,
+                theme,
+                diagnostics,
+                export_format: config.export,
+                output_dir,
+            },
+            alert_dispatcher: AlertDispatcher::new(config.alert_sinks),
+            toasts: Vec::new(),
+            keymap: Keymap::new(config.keymap),
+            show_help: false,
+            palette: None,
+            menu_area: Rect::default(),
+            screenshot_format: config.screenshot,
+            screenshot: None,
+            notice: None,
+            recorder: None,
         })
     }
 
+    /// Records every rendered frame to an asciicast file at `path`.
+    pub fn with_recording(mut self, path: &Path) -> eyre::Result<Self> {
+        self.recorder = Some(Recorder::create(path)?);
+        info!(path = %path.display(), "recording");
+        Ok(self)
+    }
+
     pub fn run(mut self, mut terminal: DefaultTerminal) -> eyre::Result<()> {
-        let tick_rate = Duration::from_millis(250);
         let mut last_tick = Instant::now();
         while !self.should_exit {
-            terminal.draw(|frame| self.draw(frame))?;
-            let timeout = tick_rate.saturating_sub(last_tick.elapsed());
-            if event::poll(timeout)?
-                && let Event::Key(key) = event::read()?
+            crash::set_state(self.summary());
+            let started = Instant::now();
+            let frame = terminal.draw(|frame| self.draw(frame))?;
+            if let Some(recorder) = &mut self.recorder
+                && let Err(err) = recorder.record(frame.buffer)
             {
-                self.handle_key(key);
+                // the app goes on without recording
+                warn!("cannot record frame, recording stopped: {err:#}");
+                self.recorder = None;
+            }
+            let render_time = started.elapsed();
+            if render_time > SLOW_RENDER {
+                warn!(?render_time, page = self.page().title(), "slow render");
+            } else {
+                trace!(?render_time, "rendered");
             }
-            if last_tick.elapsed() >= tick_rate {
+            let timeout = TICK_RATE.saturating_sub(last_tick.elapsed());
+            if event::poll(timeout)? {
+                let event = event::read()?;
+                trace!(?event, "input");
+                crash::record_event(&event);
+                match event {
+                    Event::Key(key) => self.handle_key(key),
+                    Event::Mouse(mouse) => self.handle_mouse(mouse),
+                    _ => {}
+                }
+            }
+            if let Some(resolved) = self.keymap.resolve_timeout(self.context()) {
+                self.handle_resolved(self.context(), resolved);
+            }
+            if last_tick.elapsed() >= TICK_RATE {
+                let started = Instant::now();
                 self.on_tick();
+                trace!(tick_time = ?started.elapsed(), "ticked");
                 last_tick = Instant::now();
             }
         }
+        info!("exiting");
         Ok(())
     }
 
-    fn scroll_down(&mut self) {
-        match self.menu_state.selected() {
-            Some(0) => self.intro.scroll_view_state.scroll_down(),
-            Some(1) => self.dashboard.scroll_view_state.scroll_down(),
-            Some(2) => self.wallets.scroll_view_state.scroll_down(),
-            _ => unreachable!(),
+    /// Describes what is on screen for crash reports.
+    fn summary(&self) -> String {
+        format!(
+            "page: {}\ncontext: {}\nnetwork: {} of {}\ntheme: {}\nmenu: {}, help: {}, palette: {}",
+            self.page().title(),
+            self.context(),
+            self.state.network().name,
+            self.state.networks.len(),
+            self.state.theme().name,
+            self.show_menu,
+            self.show_help,
+            self.palette.is_some(),
+        )
+    }
+
+    /// Returns the selected page.
+    fn page(&self) -> &dyn Page {
+        self.pages[self.menu_state.selected().unwrap_or_default()].as_ref()
+    }
+
+    fn page_mut(&mut self) -> &mut dyn Page {
+        self.pages[self.menu_state.selected().unwrap_or_default()].as_mut()
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let position = Position::new(mouse.column, mouse.row);
+        if self.menu_area.contains(position) {
+            if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                // every menu item is 3 lines high
+                let index =
+                    self.menu_state.offset() + usize::from((position.y - self.menu_area.y) / 3);
+                if index < self.pages.len() {
+                    self.menu_state.select(Some(index));
+                }
+            }
+            return;
         }
+
+        let index = self.menu_state.selected().unwrap_or_default();
+        let page = self.pages[index].as_mut();
+        if !page.handle_mouse(mouse, &self.state) {
+            match mouse.kind {
+                MouseEventKind::ScrollDown => page.scroll_down(),
+                MouseEventKind::ScrollUp => page.scroll_up(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the key binding context of the selected page.
+    fn context(&self) -> Context {
+        if self.palette.is_some() {
+            return Context::Search;
+        }
+        self.page().context()
     }
 
-    fn scroll_up(&mut self) {
-        match self.menu_state.selected() {
-            Some(0) => self.intro.scroll_view_state.scroll_up(),
-            Some(1) => self.dashboard.scroll_view_state.scroll_up(),
-            Some(2) => self.wallets.scroll_view_state.scroll_up(),
-            _ => unreachable!(),
+    /// Returns the actions that can be triggered on the selected page, the
+    /// most relevant first.
+    fn actions(&self) -> Vec<Action> {
+        if self.palette.is_some() {
+            return vec![
+                Action::Submit,
+                Action::ScrollDown,
+                Action::ScrollUp,
+                Action::Unfocus,
+            ];
+        }
+        let page = self.page().actions();
+        if self.context() == Context::Search {
+            return page.to_vec();
+        }
+        let mut actions = vec![Action::ShowHelp, Action::OpenPalette];
+        for action in page.iter().chain(&[
+            Action::NextPage,
+            Action::PreviousPage,
+            Action::ScrollDown,
+            Action::ScrollUp,
+            Action::NextNetwork,
+            Action::NextTheme,
+            Action::Screenshot,
+            Action::ToggleMenu,
+            Action::Quit,
+        ]) {
+            if !actions.contains(action) {
+                actions.push(*action);
+            }
         }
+        actions
     }
 
     fn handle_key(&mut self, key: KeyEvent) {
@@ -97,69 +336,268 @@ impl App {
             return;
         }
 
-        // search box key handling
-        if self.menu_state.selected() == Some(2) && self.wallets.wallets_table_state.search_focused
-        {
-            match key.code {
-                KeyCode::Enter => {
-                    // TODO search
-                }
-                KeyCode::Esc | KeyCode::Tab | KeyCode::BackTab => {
-                    self.wallets.wallets_table_state.search_focused = false;
-                }
-                _ => {
-                    self.wallets
-                        .wallets_table_state
-                        .search_state
-                        .handle_events(key.code, key.modifiers);
-                }
-            }
+        if self.show_help {
+            self.show_help = false;
             return;
         }
 
-        // fall through to general key handling
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => self.should_exit = true,
-            KeyCode::Char('m') => self.show_menu = !self.show_menu,
-            KeyCode::Char('j') | KeyCode::Down | KeyCode::PageDown => {
-                self.scroll_down();
+        let context = self.context();
+        for resolved in self.keymap.resolve(context, key) {
+            self.handle_resolved(context, resolved);
+        }
+    }
+
+    /// Handles the outcome of a key sequence pressed in `context`.
+    fn handle_resolved(&mut self, context: Context, resolved: Resolved) {
+        match resolved {
+            Resolved::Actions(actions) => {
+                // the page gets the first chance to handle the action of the most specific context
+                for action in actions {
+                    if self.handle_page_action(action) || self.handle_action(action) {
+                        break;
+                    }
+                }
             }
-            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.scroll_down();
+            Resolved::Pending => {}
+            Resolved::Unbound(keys) if context == Context::Search => {
+                for key in keys {
+                    if let Some(palette) = &mut self.palette {
+                        palette.handle_input(key);
+                        continue;
+                    }
+                    self.page_mut().handle_input(key);
+                }
             }
-            KeyCode::Char('k') | KeyCode::Up | KeyCode::PageUp => {
-                self.scroll_up();
+            Resolved::Unbound(_) => {}
+        }
+    }
+
+    /// Handles `action` while the command palette is open, returns whether it
+    /// was consumed.
+    fn handle_palette_action(&mut self, action: Action) -> bool {
+        let Some(palette) = &mut self.palette else {
+            return false;
+        };
+        match action {
+            Action::ScrollDown => palette.select_next(),
+            Action::ScrollUp => palette.select_previous(),
+            Action::Unfocus => self.palette = None,
+            Action::Submit => {
+                let kind = palette.selected().map(|command| command.kind.clone());
+                self.palette = None;
+                if let Some(kind) = kind {
+                    self.run_command(kind);
+                }
             }
-            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.scroll_up();
+            _ => {}
+        }
+        true
+    }
+
+    /// Returns the commands offered by the command palette.
+    fn commands(&self) -> Vec<Command> {
+        let network = self.state.network();
+        let mut commands = self
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| Command::new(page.title(), "Page", CommandKind::Page(i)))
+            .collect::<Vec<_>>();
+        commands.extend(self.state.networks.iter().enumerate().map(|(i, network)| {
+            Command::new(
+                format!("Switch to {}", network.name),
+                "Network",
+                CommandKind::Network(i),
+            )
+        }));
+        commands.extend(self.state.themes.iter().enumerate().map(|(i, theme)| {
+            Command::new(
+                format!("Theme: {}", theme.name),
+                "Theme",
+                CommandKind::Theme(i),
+            )
+        }));
+        commands.extend(ExportFormat::ALL.into_iter().map(|format| {
+            Command::new(
+                format!("Export as {}", format.name()),
+                "Export",
+                CommandKind::Export(format),
+            )
+        }));
+        commands.extend(ScreenshotFormat::ALL.into_iter().map(|format| {
+            Command::new(
+                format!("Screenshot as {}", format.name()),
+                "Screenshot",
+                CommandKind::Screenshot(format),
+            )
+        }));
+        commands.extend(
+            network
+                .wallets
+                .iter()
+                .enumerate()
+                .map(|(i, wallet)| Command::new(&wallet.address, "Wallet", CommandKind::Wallet(i))),
+        );
+        let mut nodes = network
+            .mpc_logs
+            .iter()
+            .map(|entry| entry.source.as_str())
+            .collect::<Vec<_>>();
+        nodes.sort_unstable();
+        nodes.dedup();
+        commands.extend(nodes.into_iter().map(|node| {
+            Command::new(
+                format!("Logs of {node}"),
+                "Node",
+                CommandKind::Node(node.to_string()),
+            )
+        }));
+        commands.extend(TIME_RANGES.iter().map(|(name, range)| {
+            Command::new(
+                format!("Logs of the last {name}"),
+                "Time range",
+                CommandKind::Since(*range),
+            )
+        }));
+        commands.extend(TIME_RANGES.iter().map(|(name, range)| {
+            Command::new(
+                format!("Charts of the last {name}"),
+                "Time range",
+                CommandKind::ChartRange(Some(*range)),
+            )
+        }));
+        commands.push(Command::new(
+            "Live charts",
+            "Time range",
+            CommandKind::ChartRange(None),
+        ));
+        commands.extend(self.palette_actions().into_iter().map(|action| {
+            Command::new(action.description(), "Action", CommandKind::Action(action))
+        }));
+        commands
+    }
+
+    /// Returns the actions offered in the command palette, those of the
+    /// selected page in any context first.
+    fn palette_actions(&self) -> Vec<Action> {
+        let mut actions = self.page().palette_actions().to_vec();
+        for action in [
+            Action::ShowHelp,
+            Action::NextPage,
+            Action::PreviousPage,
+            Action::ScrollDown,
+            Action::ScrollUp,
+            Action::NextNetwork,
+            Action::NextTheme,
+            Action::Screenshot,
+            Action::ToggleMenu,
+            Action::Quit,
+        ] {
+            if !actions.contains(&action) {
+                actions.push(action);
             }
-            KeyCode::Char('s') | KeyCode::Char('f') => {
-                if self.menu_state.selected() == Some(2) {
-                    self.wallets.wallets_table_state.search_focused = true;
+        }
+        actions
+    }
+
+    fn run_command(&mut self, kind: CommandKind) {
+        match kind {
+            CommandKind::Page(index) => self.menu_state.select(Some(index)),
+            CommandKind::Network(index) => self.select_network(index),
+            CommandKind::Theme(index) => self.state.theme = index,
+            CommandKind::Screenshot(format) => self.screenshot = Some(format),
+            CommandKind::Export(format) => {
+                // exports the data of the selected page only
+                let index = self.menu_state.selected().unwrap_or_default();
+                if !self.pages[index].run_command(&kind, &self.state) {
+                    self.notice = Some((
+                        Err(format!("No {} export on this page", format.name())),
+                        Instant::now(),
+                    ));
                 }
             }
-            KeyCode::Tab => {
-                if self.menu_state.selected() == Some(2) {
-                    self.menu_state.select_first();
-                } else {
-                    self.menu_state.select_next();
+            CommandKind::Action(action) => {
+                let index = self.menu_state.selected().unwrap_or_default();
+                let page = &mut self.pages[index];
+                if !(page.palette_actions().contains(&action)
+                    && page.run_palette_action(action, &self.state))
+                {
+                    self.handle_action(action);
                 }
             }
-            KeyCode::BackTab => {
-                if self.menu_state.selected() == Some(0) {
-                    self.menu_state.select_last();
-                } else {
-                    self.menu_state.select_previous();
+            kind => {
+                // the first page that handles the command is shown
+                if let Some(index) = self
+                    .pages
+                    .iter_mut()
+                    .position(|page| page.run_command(&kind, &self.state))
+                {
+                    self.menu_state.select(Some(index));
                 }
             }
-            _ => {}
         }
     }
 
+    fn select_network(&mut self, index: usize) {
+        let previous = mem::replace(&mut self.state.network, index);
+        if previous != index {
+            for page in &mut self.pages {
+                page.on_network_change(previous, index);
+            }
+        }
+    }
+
+    fn handle_page_action(&mut self, action: Action) -> bool {
+        if self.palette.is_some() {
+            return self.handle_palette_action(action);
+        }
+        let index = self.menu_state.selected().unwrap_or_default();
+        self.pages[index].handle_action(action, &self.state)
+    }
+
+    /// Handles actions that apply to every page, returns whether `action` was consumed.
+    fn handle_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => self.should_exit = true,
+            Action::ToggleMenu => self.show_menu = !self.show_menu,
+            Action::ShowHelp => self.show_help = true,
+            Action::OpenPalette => self.palette = Some(CommandPaletteState::new(self.commands())),
+            Action::NextNetwork => {
+                self.select_network((self.state.network + 1) % self.state.networks.len());
+            }
+            Action::NextTheme => {
+                self.state.theme = (self.state.theme + 1) % self.state.themes.len();
+            }
+            Action::Screenshot => self.screenshot = Some(self.screenshot_format),
+            Action::ScrollDown => self.page_mut().scroll_down(),
+            Action::ScrollUp => self.page_mut().scroll_up(),
+            Action::NextPage => {
+                let selected = self.menu_state.selected().unwrap_or_default();
+                self.menu_state
+                    .select(Some((selected + 1) % self.pages.len()));
+            }
+            Action::PreviousPage => {
+                let selected = self.menu_state.selected().unwrap_or_default();
+                let pages = self.pages.len();
+                self.menu_state.select(Some((selected + pages - 1) % pages));
+            }
+            _ => return false,
+        }
+        true
+    }
+
     fn on_tick(&mut self) {
-        self.intro.on_tick();
-        self.dashboard.on_tick();
-        self.wallets.on_tick();
+        for page in &mut self.pages {
+            page.on_tick();
+        }
+        let events = update(
+            &mut self.state.networks,
+            &mut self.state.alert_engine,
+            &self.alert_dispatcher,
+        );
+        let now = chrono::Local::now();
+        self.toasts.retain(|event| now - event.at < TOAST_DURATION);
+        self.toasts.extend(events);
     }
 
     fn draw(&mut self, frame: &mut Frame) {
@@ -173,42 +611,104 @@ impl App {
                 Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(menu);
             menu_bot.width -= 1; // Adjust for right border
             let [logo, title] =
-                Layout::horizontal([Constraint::Length(5), Constraint::Fill(1)]).areas(menu_top);
+                Layout::horizontal([Constraint::Length(self.logo.width()), Constraint::Fill(1)])
+                    .areas(menu_top);
 
             Block::new().borders(Borders::RIGHT).render(menu, buf);
-            ratatui_image::Image::new(&self.image).render(logo, buf);
+            self.logo.render(logo, buf, self.state.theme());
             self.title().render(title, buf);
             self.render_menu(menu_bot, buf);
+            self.menu_area = menu_bot;
             // hacky way to have left margin for content when menu is shown
             content
                 .offset(Offset::new(1, 0))
                 .resize(Size::new(content.width - 1, content.height))
         } else {
+            self.menu_area = Rect::default();
             area
         };
+        let [content, status_bar, footer] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(content);
 
-        match self.menu_state.selected() {
-            Some(0) => self.intro.render(content, buf),
-            Some(1) => self.dashboard.render(content, buf),
-            Some(2) => self.wallets.render(content, buf),
-            _ => {}
+        let index = self.menu_state.selected().unwrap_or_default();
+        self.pages[index].render(content, buf, &self.state);
+        if let Some(format) = self.screenshot.take() {
+            self.take_screenshot(content, buf, format);
+        }
+        if let Some(notice) = self.pages[index].take_notice() {
+            self.notice = Some((notice, Instant::now()));
+        }
+
+        let theme = self.state.theme();
+        let context = self.context();
+        let actions = self.actions();
+        StatusBar::new(&self.state.networks, self.state.network, theme).render(status_bar, buf);
+        match &self.notice {
+            Some((notice, at)) if at.elapsed() < NOTICE_DURATION => {
+                let (text, color) = match notice {
+                    Ok(text) => (text, theme.success),
+                    Err(text) => (text, theme.error),
+                };
+                Line::styled(text.as_str(), Style::new().fg(color))
+                    .render(footer.inner(Margin::new(1, 0)), buf);
+            }
+            _ => KeyHints::new(self.keymap.help(context, &actions), theme).render(footer, buf),
+        }
+        if self.show_help {
+            Help::new(context, self.keymap.help(context, &actions), theme).render(area, buf);
+        }
+        if let Some(palette) = &mut self.palette {
+            CommandPalette::new(theme).render(area, buf, palette);
+        }
+
+        Toasts::new(&self.toasts, theme).render(area, buf);
+    }
+
+    /// Saves the page rendered to `area` of `buf` in `format`.
+    fn take_screenshot(&mut self, area: Rect, buf: &Buffer, format: ScreenshotFormat) {
+        let mut page = Buffer::empty(area);
+        for position in area.positions() {
+            page[position] = buf[position].clone();
         }
+        let notice =
+            match screenshot::save(&page, self.page().title(), format, &self.state.output_dir) {
+                Ok(path) => {
+                    info!(path = %path.display(), "saved screenshot");
+                    Ok(format!("Saved screenshot to {}", path.display()))
+                }
+                Err(err) => {
+                    warn!("cannot save screenshot: {err:#}");
+                    Err(format!("Screenshot failed: {err:#}"))
+                }
+            };
+        self.notice = Some((notice, Instant::now()));
     }
 
     fn title(&self) -> impl Widget {
         Paragraph::new(vec![
-            Line::styled("Merces by TACEO", Style::new().bold()),
+            Line::from(vec![
+                Span::styled("Merces by TACEO", Style::new().bold()),
+                Span::raw(" "),
+                Span::styled(
+                    format!(" {} ", self.state.network().name),
+                    self.state.theme().selected(),
+                ),
+            ]),
             Line::raw("Confidential Tokens"),
         ])
     }
 
     fn render_menu(&mut self, area: Rect, buf: &mut Buffer) {
-        let list = List::new([
-            "\t\n   Introduction\n\t",
-            "\t\n   Dashboard\n\t",
-            "\t\n   Wallets\n\t",
-        ])
-        .highlight_style(SELECTED_STYLE);
+        let list = List::new(
+            self.pages
+                .iter()
+                .map(|page| format!("\t\n {}  {}\n\t", page.icon(), page.title())),
+        )
+        .highlight_style(self.state.theme().selected());
         StatefulWidget::render(list, area, buf, &mut self.menu_state);
     }
 }
@@ -240,3 +740,59 @@ impl Iterator for SinSignal {
         Some(point)
     }
 }
+
+/// Creates the configured networks, or the default ones if none are
+/// configured, with their history if it is enabled.
+fn load_networks(configs: Vec<NetworkConfig>, history: &HistoryConfig) -> Vec<Network> {
+    let configs = if configs.is_empty() {
+        NetworkConfig::defaults()
+    } else {
+        configs
+    };
+    let history_dir = history.dir().filter(|_| history.enabled);
+    configs
+        .into_iter()
+        .enumerate()
+        .map(|(index, config)| {
+            let network = Network::new(index, config);
+            match &history_dir {
+                Some(dir) => network.with_history(dir, history.retention()),
+                None => network,
+            }
+        })
+        .collect()
+}
+
+/// Updates the networks, evaluates the alert rules against them and delivers
+/// the events of alerts that changed state, which are returned.
+///
+/// Shared by the UI and the headless mode so that both aggregate the same way.
+fn update(
+    networks: &mut [Network],
+    alert_engine: &mut AlertEngine,
+    alert_dispatcher: &AlertDispatcher,
+) -> Vec<AlertEvent> {
+    for network in networks.iter_mut() {
+        network.on_tick();
+    }
+
+    let events = alert_engine.evaluate(|name, metric| {
+        networks
+            .iter()
+            .find(|network| network.name == name)
+            .and_then(|network| network.metric(metric))
+    });
+    for event in &events {
+        info!(
+            rule = %event.rule,
+            network = %event.network,
+            kind = ?event.kind,
+            "alert"
+        );
+    }
+    alert_dispatcher.dispatch(&events);
+    if let Some(result) = alert_dispatcher.last_result() {
+        alert_engine.delivery_error = result.err();
+    }
+    events
+}
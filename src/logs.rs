@@ -0,0 +1,224 @@
+use std::{fmt, ops::Range, str::FromStr};
+
+use chrono::{DateTime, Local};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Number of entries every log stream keeps for scrollback.
+pub const LOG_HISTORY: usize = 1000;
+/// Prefix of the sources of transaction log entries, followed by the block
+/// number.
+pub const BLOCK_SOURCE_PREFIX: &str = "block-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            _ => Err(format!("unknown log level `{s}`")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: LogLevel,
+    pub source: String,
+    pub message: String,
+}
+
+impl LogEntry {
+    pub fn new(level: LogLevel, source: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: Local::now(),
+            level,
+            source: source.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Filter parsed from a query like `node:coordinator level:warn timeout|failed`.
+///
+/// `node:` terms match the entry source case-insensitively and can be quoted
+/// like `node:"mpc node 1"` to include spaces, `level:` sets the
+/// minimum level and the remaining terms form a case-insensitive regex
+/// matched against the message.
+#[derive(Default)]
+pub struct LogFilter {
+    nodes: Vec<String>,
+    level: Option<LogLevel>,
+    regex: Option<Regex>,
+    pub error: Option<String>,
+}
+
+impl LogFilter {
+    pub fn parse(query: &str) -> Self {
+        let mut filter = Self::default();
+        let mut pattern = Vec::new();
+        for term in terms(query) {
+            match term.split_once(':') {
+                Some(("node", node)) => filter.nodes.push(node.trim_matches('"').to_lowercase()),
+                Some(("level", level)) => match level.parse() {
+                    Ok(level) => filter.level = Some(level),
+                    Err(err) => filter.error = Some(err),
+                },
+                _ => pattern.push(term),
+            }
+        }
+        if !pattern.is_empty() {
+            match RegexBuilder::new(&pattern.join(" "))
+                .case_insensitive(true)
+                .build()
+            {
+                Ok(regex) => filter.regex = Some(regex),
+                Err(err) => filter.error = Some(err.to_string()),
+            }
+        }
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.level.is_none() && self.regex.is_none()
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        let source = entry.source.to_lowercase();
+        (self.nodes.is_empty() || self.nodes.iter().any(|node| source.contains(node)))
+            && self.level.is_none_or(|level| entry.level >= level)
+            && self
+                .regex
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&entry.message))
+    }
+
+    /// Returns the byte ranges of `message` matched by the regex.
+    pub fn hits(&self, message: &str) -> Vec<Range<usize>> {
+        self.regex
+            .as_ref()
+            .map(|regex| {
+                regex
+                    .find_iter(message)
+                    .map(|hit| hit.range())
+                    .filter(|range| !range.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Splits `query` at whitespace outside of double quotes.
+fn terms(query: &str) -> Vec<&str> {
+    let mut terms = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    for (i, c) in query.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        match (c.is_whitespace() && !quoted, start) {
+            (true, Some(s)) => {
+                terms.push(&query[s..i]);
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        terms.push(&query[s..]);
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: LogLevel, source: &str, message: &str) -> LogEntry {
+        LogEntry::new(level, source, message)
+    }
+
+    #[test]
+    fn splits_terms_outside_of_quotes() {
+        assert_eq!(terms(""), Vec::<&str>::new());
+        assert_eq!(terms("  a   b "), ["a", "b"]);
+        assert_eq!(
+            terms(r#"node:"mpc node 1" level:warn time out"#),
+            [r#"node:"mpc node 1""#, "level:warn", "time", "out"]
+        );
+        // an unterminated quote runs to the end of the query
+        assert_eq!(terms(r#"node:"mpc node"#), [r#"node:"mpc node"#]);
+    }
+
+    #[test]
+    fn matches_quoted_nodes_case_insensitively() {
+        let filter = LogFilter::parse(r#"node:"MPC Node 1""#);
+        assert!(filter.error.is_none());
+        assert!(filter.matches(&entry(LogLevel::Info, "MPC Node 1", "started")));
+        assert!(!filter.matches(&entry(LogLevel::Info, "MPC Node 2", "started")));
+
+        let filter = LogFilter::parse("node:coordinator node:block-");
+        assert!(filter.matches(&entry(LogLevel::Info, "MPC Coordinator", "started")));
+        assert!(filter.matches(&entry(LogLevel::Info, "block-12", "included")));
+        assert!(!filter.matches(&entry(LogLevel::Info, "MPC Node 1", "started")));
+    }
+
+    #[test]
+    fn level_sets_the_minimum_level() {
+        let filter = LogFilter::parse("level:warning");
+        assert!(filter.error.is_none());
+        assert!(!filter.matches(&entry(LogLevel::Info, "MPC Node 1", "started")));
+        assert!(filter.matches(&entry(LogLevel::Warn, "MPC Node 1", "slow")));
+        assert!(filter.matches(&entry(LogLevel::Error, "MPC Node 1", "failed")));
+
+        let filter = LogFilter::parse("level:loud");
+        assert_eq!(filter.error.as_deref(), Some("unknown log level `loud`"));
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn remaining_terms_form_a_regex() {
+        let filter = LogFilter::parse("level:info proof (failed|TIMED)");
+        assert!(filter.error.is_none());
+        assert!(filter.matches(&entry(LogLevel::Info, "MPC Node 1", "Proof timed out")));
+        assert!(!filter.matches(&entry(LogLevel::Info, "MPC Node 1", "proof done")));
+        assert_eq!(filter.hits("proof failed, proof timed"), [0..12, 14..25]);
+    }
+
+    #[test]
+    fn reports_invalid_regexes() {
+        let filter = LogFilter::parse("node:coordinator (unclosed");
+        assert!(filter.error.is_some());
+        assert!(filter.regex.is_none());
+        // the valid terms still apply
+        assert!(!filter.is_empty());
+        assert!(filter.hits("(unclosed").is_empty());
+    }
+}
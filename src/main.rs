@@ -1,8 +1,64 @@
-use taceo_merces_tui::App;
+use std::{path::PathBuf, time::Duration};
+
+use clap::Parser;
+use ratatui::crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+};
+use taceo_merces_tui::{
+    App,
+    config::Config,
+    crash,
+    diagnostics::Diagnostics,
+    headless::{Headless, OutputFormat},
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Path to the config file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// Log debug events to the log file, twice for trace events
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Record the session as an asciicast v2 file
+    #[arg(long, value_name = "FILE", conflicts_with = "headless")]
+    record: Option<PathBuf>,
+    /// Print snapshots of the metrics to stdout instead of drawing the UI
+    #[arg(long)]
+    headless: bool,
+    /// Output format of the headless mode
+    #[arg(long, value_enum, default_value_t, requires = "headless")]
+    format: OutputFormat,
+    /// Seconds between two snapshots in headless mode
+    #[arg(long, default_value_t = 1.0, requires = "headless")]
+    interval: f64,
+    /// Exit after this many snapshots in headless mode
+    #[arg(long, requires = "headless")]
+    count: Option<u64>,
+}
 
 fn main() -> eyre::Result<()> {
+    let cli = Cli::parse();
+    let (diagnostics, _guard) = Diagnostics::init(cli.verbose);
+    let config = Config::load(cli.config.as_deref())?;
+    if cli.headless {
+        let interval = Duration::try_from_secs_f64(cli.interval)?;
+        return Headless::new(config, cli.format, interval, cli.count)?.run();
+    }
     let terminal = ratatui::init();
-    let app_result = App::new()?.run(terminal);
+    crash::install_hook();
+    // the terminal is restored on every error, including an invalid config
+    let app_result = execute!(std::io::stdout(), EnableMouseCapture)
+        .map_err(eyre::Report::from)
+        .and_then(|()| App::new(config, diagnostics))
+        .and_then(|app| match &cli.record {
+            Some(path) => app.with_recording(path),
+            None => Ok(app),
+        })
+        .and_then(|app| app.run(terminal));
+    let _ = execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
     app_result
 }
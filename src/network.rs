@@ -0,0 +1,619 @@
+use std::{
+    collections::VecDeque,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use chrono::TimeDelta;
+use eyre::{Context as _, eyre};
+use serde::Deserialize;
+use tracing::{info, trace, warn};
+
+use crate::{
+    SinSignal, TICK_RATE,
+    alerts::Metric,
+    history::{self, HistoryStore},
+    logs::{BLOCK_SOURCE_PREFIX, LOG_HISTORY, LogEntry, LogLevel},
+    source::{SourceKind, SourceStatus},
+    widgets::wallets_table::Wallet,
+};
+
+const TX_LOG_MESSAGE: &str = "50 transactions published in 0x1234...abcd";
+
+const MPC_LOGS: [(&str, LogLevel, &str); 16] = [
+    (
+        "MPC Coordinator",
+        LogLevel::Info,
+        "Sending job to MPC nodes",
+    ),
+    (
+        "MPC Node 1",
+        LogLevel::Info,
+        "Received MPC job for a batch of transactions of size 50",
+    ),
+    (
+        "MPC Node 2",
+        LogLevel::Info,
+        "Received MPC job for a batch of transactions of size 50",
+    ),
+    (
+        "MPC Node 3",
+        LogLevel::Info,
+        "Received MPC job for a batch of transactions of size 50",
+    ),
+    (
+        "MPC Node 1",
+        LogLevel::Debug,
+        "Finished connection establishment in 15ms",
+    ),
+    (
+        "MPC Node 2",
+        LogLevel::Warn,
+        "Slow connection establishment to MPC Node 3, retrying",
+    ),
+    (
+        "MPC Node 2",
+        LogLevel::Debug,
+        "Finished connection establishment in 45ms",
+    ),
+    (
+        "MPC Node 3",
+        LogLevel::Debug,
+        "Finished connection establishment in 15ms",
+    ),
+    ("MPC Node 1", LogLevel::Info, "Start processing job.."),
+    ("MPC Node 2", LogLevel::Info, "Start processing job.."),
+    ("MPC Node 3", LogLevel::Info, "Start processing job.."),
+    (
+        "MPC Node 1",
+        LogLevel::Info,
+        "Finished processing job in 500ms",
+    ),
+    (
+        "MPC Node 2",
+        LogLevel::Info,
+        "Finished processing job in 500ms",
+    ),
+    (
+        "MPC Node 3",
+        LogLevel::Error,
+        "Proof verification failed for job, rescheduling",
+    ),
+    (
+        "MPC Node 3",
+        LogLevel::Info,
+        "Finished processing job in 800ms",
+    ),
+    (
+        "MPC Coordinator",
+        LogLevel::Info,
+        "Job finished, publishing batch",
+    ),
+];
+
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60);
+/// How far back the charts reach without a history store.
+const CHART_HISTORY_RETENTION: Duration = Duration::from_secs(60 * 60);
+/// Upper bound of the points per chart shown for a time range, longer ranges
+/// are thinned out.
+const MAX_CHART_POINTS: usize = 1000;
+
+/// Ticks between the starts of two simulated outages of a source.
+const OUTAGE_PERIOD: u64 = 480;
+/// Ticks a simulated outage lasts, long enough to open the circuit breaker.
+const OUTAGE_DURATION: u64 = 120;
+
+/// A Merces deployment as read from the `[[networks]]` tables of the config.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkConfig {
+    pub name: String,
+    /// Let the simulated sources drop out now and then, to try the status
+    /// bar, the staleness indicators and the reconnection
+    #[serde(default)]
+    pub outages: bool,
+}
+
+impl NetworkConfig {
+    /// The deployments used when the config does not list any networks.
+    pub fn defaults() -> Vec<Self> {
+        ["Base", "Arc"]
+            .into_iter()
+            .map(|name| Self {
+                name: name.to_string(),
+                outages: false,
+            })
+            .collect()
+    }
+}
+
+/// One point per tick of every chart, the points the history store records,
+/// for time ranges longer than the live window.
+pub struct ChartHistory {
+    txs_per_second: VecDeque<(f64, f64)>,
+    node_cpu: VecDeque<(f64, f64)>,
+    node_net_up: VecDeque<(f64, f64)>,
+    node_net_down: VecDeque<(f64, f64)>,
+    /// Number of points kept per chart
+    len: usize,
+}
+
+impl ChartHistory {
+    fn new(retention: Duration) -> Self {
+        Self {
+            txs_per_second: VecDeque::new(),
+            node_cpu: VecDeque::new(),
+            node_net_up: VecDeque::new(),
+            node_net_down: VecDeque::new(),
+            len: (retention.as_millis() / TICK_RATE.as_millis()) as usize,
+        }
+    }
+
+    /// Appends a point to every chart in the order of
+    /// [`ChartHistory::since`].
+    fn push(&mut self, points: [Option<(f64, f64)>; 4]) {
+        let len = self.len;
+        let series = [
+            &mut self.txs_per_second,
+            &mut self.node_cpu,
+            &mut self.node_net_up,
+            &mut self.node_net_down,
+        ];
+        for (series, point) in series.into_iter().zip(points) {
+            series.extend(point);
+            if series.len() > len {
+                series.pop_front();
+            }
+        }
+    }
+
+    /// Returns the points of the transactions per second, node CPU, upstream
+    /// and downstream charts from `start` on, at most `max` per chart.
+    fn since(&self, start: f64, max: usize) -> [Vec<(f64, f64)>; 4] {
+        [
+            &self.txs_per_second,
+            &self.node_cpu,
+            &self.node_net_up,
+            &self.node_net_down,
+        ]
+        .map(|series| {
+            let from = series.partition_point(|(x, _)| *x < start);
+            let step = (series.len() - from).div_ceil(max).max(1);
+            series.range(from..).step_by(step).copied().collect()
+        })
+    }
+}
+
+/// Data of a single deployment, updated on every tick whether it is shown or not.
+pub struct Network {
+    pub name: String,
+    pub window: [f64; 2],
+    txs_per_second_signal: SinSignal,
+    pub txs_per_second_data: Vec<(f64, f64)>,
+    pub network_util: f64,
+    node_cpu_signal: SinSignal,
+    pub node_cpu_data: Vec<(f64, f64)>,
+    node_net_up_signal: SinSignal,
+    pub node_net_up_data: Vec<(f64, f64)>,
+    node_net_down_signal: SinSignal,
+    pub node_net_down_data: Vec<(f64, f64)>,
+    chart_history: ChartHistory,
+    pub total_txs: u64,
+    pub avg_tx_latency: f64,
+    pub avg_proof_time: f64,
+    pub tx_logs: Vec<LogEntry>,
+    pub mpc_logs: Vec<LogEntry>,
+    /// Sources of the MPC logs in order of appearance, their position picks
+    /// their color
+    pub log_sources: Vec<String>,
+    tx_log_count: usize,
+    mpc_log_count: usize,
+    pub wallets: Vec<Wallet>,
+    history: Option<HistoryStore>,
+    /// Error of the last failed write to the history, recording goes on with
+    /// the next tick
+    pub history_error: Option<String>,
+    last_snapshot: Option<Instant>,
+    /// Status of every source in the order of [`SourceKind::ALL`]
+    pub sources: Vec<SourceStatus>,
+    outages: bool,
+    ticks: u64,
+}
+
+impl Network {
+    /// Creates the network at position `index` of the configured networks.
+    ///
+    /// The simulated signals are stretched by `index` so that deployments can
+    /// be told apart.
+    pub fn new(index: usize, config: NetworkConfig) -> Self {
+        let stretch = 1.0 + 0.25 * index as f64;
+        let mut txs_per_second_signal = SinSignal::new(0.1, 2.0 * stretch, 10.0);
+        let txs_per_second_data = txs_per_second_signal
+            .by_ref()
+            .take(200)
+            .collect::<Vec<(f64, f64)>>();
+        let mut node_cpu_signal = SinSignal::new(0.1, 3.0 * stretch, 10.0);
+        let node_cpu_data = node_cpu_signal
+            .by_ref()
+            .take(200)
+            .collect::<Vec<(f64, f64)>>();
+        let mut node_net_up_signal = SinSignal::new(0.1, 1.0 * stretch, 10.0);
+        let node_net_up_data = node_net_up_signal
+            .by_ref()
+            .take(200)
+            .collect::<Vec<(f64, f64)>>();
+        let mut node_net_down_signal = SinSignal::new(0.1, 2.5 * stretch, 10.0);
+        let node_net_down_data = node_net_down_signal
+            .by_ref()
+            .take(200)
+            .collect::<Vec<(f64, f64)>>();
+
+        let mut network = Self {
+            name: config.name,
+            window: [0.0, 20.0],
+            txs_per_second_signal,
+            txs_per_second_data,
+            network_util: 0.0,
+            node_cpu_signal,
+            node_cpu_data,
+            node_net_up_signal,
+            node_net_up_data,
+            node_net_down_signal,
+            node_net_down_data,
+            chart_history: ChartHistory::new(CHART_HISTORY_RETENTION),
+            total_txs: 1_000_000,
+            avg_tx_latency: 200.0,
+            avg_proof_time: 5.2,
+            tx_logs: Vec::new(),
+            mpc_logs: Vec::new(),
+            log_sources: Vec::new(),
+            tx_log_count: 0,
+            mpc_log_count: 0,
+            wallets: (0..20)
+                .map(|i| Wallet {
+                    address: format!("0x1234...{:04x}", i),
+                    last_transfer: format!("2024/09/{:02} 12:34", i + 1),
+                    transferred_amount: (i + 1) * 1000,
+                    balance: (20 - i) * 5000,
+                })
+                .collect(),
+            history: None,
+            history_error: None,
+            last_snapshot: None,
+            sources: SourceKind::ALL.map(SourceStatus::new).to_vec(),
+            outages: config.outages,
+            ticks: 0,
+        };
+        for _ in 0..MPC_LOGS.len() {
+            network.next_tx_log();
+            network.next_mpc_log();
+        }
+        network
+    }
+
+    /// Opens the history of this network in `dir`, restores the most recent
+    /// data from it and records all further updates.
+    ///
+    /// If the history cannot be opened or read the network goes on without
+    /// it and keeps the error in [`Network::history_error`].
+    pub fn with_history(mut self, dir: &Path, retention: Duration) -> Self {
+        let dir = dir.join(history::dir_name(&self.name));
+        self.chart_history = ChartHistory::new(retention);
+        let opened = HistoryStore::open(dir, retention)
+            .and_then(|history| self.restore(&history).map(|()| history))
+            .wrap_err_with(|| format!("cannot open history of network {}", self.name));
+        match opened {
+            Ok(history) => {
+                info!(network = %self.name, "restored history");
+                self.history = Some(history);
+            }
+            Err(err) => {
+                warn!(network = %self.name, "history disabled: {err:#}");
+                self.history_error = Some(format!("{err:#}"));
+            }
+        }
+        self
+    }
+
+    /// Restores the charts over the retention with the live window ending at
+    /// the last stored point, the latest logs and the latest wallet snapshot.
+    fn restore(&mut self, history: &HistoryStore) -> eyre::Result<()> {
+        let len = self.chart_history.len;
+        let txs_per_second = history.load_tail::<(f64, f64)>(history::TXS_PER_SECOND, len)?;
+        let node_cpu = history.load_tail::<(f64, f64)>(history::NODE_CPU, len)?;
+        let node_net_up = history.load_tail::<(f64, f64)>(history::NODE_NET_UP, len)?;
+        let node_net_down = history.load_tail::<(f64, f64)>(history::NODE_NET_DOWN, len)?;
+        if let Some(&(end, _)) = txs_per_second.last()
+            && [&node_cpu, &node_net_up, &node_net_down]
+                .iter()
+                .all(|points| !points.is_empty())
+        {
+            let end = end.ceil();
+            self.window = [end - (self.window[1] - self.window[0]), end];
+            let start = self.window[0];
+            restore_series(
+                &mut self.txs_per_second_signal,
+                &mut self.txs_per_second_data,
+                &txs_per_second,
+                start,
+            );
+            restore_series(
+                &mut self.node_cpu_signal,
+                &mut self.node_cpu_data,
+                &node_cpu,
+                start,
+            );
+            restore_series(
+                &mut self.node_net_up_signal,
+                &mut self.node_net_up_data,
+                &node_net_up,
+                start,
+            );
+            restore_series(
+                &mut self.node_net_down_signal,
+                &mut self.node_net_down_data,
+                &node_net_down,
+                start,
+            );
+            self.network_util =
+                ((self.txs_per_second_data.last().unwrap().1 + 10.0) / 20.0).clamp(0.0, 1.0);
+            self.chart_history.txs_per_second = txs_per_second.into();
+            self.chart_history.node_cpu = node_cpu.into();
+            self.chart_history.node_net_up = node_net_up.into();
+            self.chart_history.node_net_down = node_net_down.into();
+        }
+
+        restore_logs(
+            &mut self.tx_logs,
+            history.load_tail(history::TX_LOGS, LOG_HISTORY)?,
+        );
+        restore_logs(
+            &mut self.mpc_logs,
+            history.load_tail(history::MPC_LOGS, LOG_HISTORY)?,
+        );
+        self.log_sources.clear();
+        for entry in &self.mpc_logs {
+            add_source(&mut self.log_sources, &entry.source);
+        }
+        if let Some(wallets) = history.load_tail::<Vec<Wallet>>(history::WALLETS, 1)?.pop() {
+            self.wallets = wallets;
+        }
+        Ok(())
+    }
+
+    /// Appends the data of the sources that delivered on this tick, in the
+    /// order of [`SourceKind::ALL`].
+    fn record(&mut self, delivered: [bool; 4]) -> eyre::Result<()> {
+        let [metrics, blocks, mpc_logs, wallets] = delivered;
+        let Some(history) = self.history.as_mut() else {
+            return Ok(());
+        };
+        // the charts are stored downsampled to their last point per tick
+        if metrics {
+            history.append(history::TXS_PER_SECOND, self.txs_per_second_data.last())?;
+            history.append(history::NODE_CPU, self.node_cpu_data.last())?;
+            history.append(history::NODE_NET_UP, self.node_net_up_data.last())?;
+            history.append(history::NODE_NET_DOWN, self.node_net_down_data.last())?;
+        }
+        if blocks {
+            history.append(history::TX_LOGS, self.tx_logs.last())?;
+        }
+        if mpc_logs {
+            history.append(history::MPC_LOGS, self.mpc_logs.last())?;
+        }
+        if wallets
+            && self
+                .last_snapshot
+                .is_none_or(|last| last.elapsed() >= SNAPSHOT_INTERVAL)
+        {
+            history.append(history::WALLETS, [&self.wallets])?;
+            self.last_snapshot = Some(Instant::now());
+        }
+        history.on_tick()
+    }
+
+    /// Returns the status of the source of `kind`.
+    pub fn source(&self, kind: SourceKind) -> &SourceStatus {
+        self.sources
+            .iter()
+            .find(|source| source.kind == kind)
+            .expect("every source kind has a status")
+    }
+
+    pub fn has_history(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// Returns the x range and the points of the transactions per second,
+    /// node CPU, upstream and downstream charts over the last `range`, or over
+    /// the live window if unset.
+    pub fn charts(&self, range: Option<TimeDelta>) -> ([f64; 2], [Vec<(f64, f64)>; 4]) {
+        match range {
+            None => (
+                self.window,
+                [
+                    self.txs_per_second_data.clone(),
+                    self.node_cpu_data.clone(),
+                    self.node_net_up_data.clone(),
+                    self.node_net_down_data.clone(),
+                ],
+            ),
+            Some(range) => {
+                // the window advances by one per tick
+                let ticks = range.num_milliseconds() as f64 / TICK_RATE.as_millis() as f64;
+                let end = self.window[1];
+                let start = end - ticks;
+                (
+                    [start, end],
+                    self.chart_history.since(start, MAX_CHART_POINTS),
+                )
+            }
+        }
+    }
+
+    /// Returns the latest value of `metric`.
+    pub fn metric(&self, metric: Metric) -> Option<f64> {
+        let last = |data: &[(f64, f64)]| data.last().map(|(_, y)| *y);
+        match metric {
+            Metric::Tps => last(&self.txs_per_second_data),
+            Metric::NetworkUtil => Some(self.network_util * 100.0),
+            Metric::NodeCpu => last(&self.node_cpu_data),
+            Metric::NodeNetUp => last(&self.node_net_up_data),
+            Metric::NodeNetDown => last(&self.node_net_down_data),
+            Metric::TotalTxs => Some(self.total_txs as f64),
+            Metric::AvgTxLatency => Some(self.avg_tx_latency),
+            Metric::AvgProofTime => Some(self.avg_proof_time),
+        }
+    }
+
+    /// Fetches from every source that is not backing off, the data of the
+    /// others stays as it is.
+    ///
+    /// Never fails, failed fetches and history writes are kept in the status
+    /// of the source and in [`Network::history_error`].
+    pub fn on_tick(&mut self) {
+        self.ticks += 1;
+        let delivered = SourceKind::ALL.map(|kind| self.poll(kind));
+        let [metrics, blocks, mpc_logs, _] = delivered;
+        if metrics {
+            self.next_metrics();
+            self.chart_history.push([
+                self.txs_per_second_data.last().copied(),
+                self.node_cpu_data.last().copied(),
+                self.node_net_up_data.last().copied(),
+                self.node_net_down_data.last().copied(),
+            ]);
+        }
+        if blocks {
+            self.next_tx_log();
+        }
+        if mpc_logs {
+            self.next_mpc_log();
+        }
+        trace!(network = %self.name, "updated");
+
+        if self.history.is_some() {
+            match self.record(delivered) {
+                Ok(()) => self.history_error = None,
+                Err(err) => {
+                    warn!(network = %self.name, "cannot record history: {err:#}");
+                    self.history_error = Some(format!("{err:#}"));
+                }
+            }
+        }
+    }
+
+    /// Fetches from the source of `kind` and returns whether it delivered.
+    fn poll(&mut self, kind: SourceKind) -> bool {
+        let index = SourceKind::ALL
+            .iter()
+            .position(|k| *k == kind)
+            .expect("every source kind is listed");
+        // the simulated sources deliver on every tick unless they are out
+        let outage = self.outages && simulated_outage(self.ticks, index as u64);
+        self.sources[index]
+            .poll(&self.name, || {
+                if outage {
+                    Err(eyre!("connection refused (simulated outage)"))
+                } else {
+                    Ok(())
+                }
+            })
+            .is_some()
+    }
+
+    fn next_metrics(&mut self) {
+        self.window[0] += 1.0;
+        self.window[1] += 1.0;
+
+        let start = self.window[0];
+        advance(
+            &mut self.txs_per_second_data,
+            &mut self.txs_per_second_signal,
+            start,
+        );
+        self.network_util =
+            ((self.txs_per_second_data.last().unwrap().1 + 10.0) / 20.0).clamp(0.0, 1.0);
+        advance(&mut self.node_cpu_data, &mut self.node_cpu_signal, start);
+        advance(
+            &mut self.node_net_up_data,
+            &mut self.node_net_up_signal,
+            start,
+        );
+        advance(
+            &mut self.node_net_down_data,
+            &mut self.node_net_down_signal,
+            start,
+        );
+    }
+
+    fn next_tx_log(&mut self) {
+        push_log(
+            &mut self.tx_logs,
+            LogEntry::new(
+                LogLevel::Info,
+                format!("{BLOCK_SOURCE_PREFIX}{}", self.tx_log_count),
+                TX_LOG_MESSAGE,
+            ),
+        );
+        self.tx_log_count += 1;
+    }
+
+    fn next_mpc_log(&mut self) {
+        let (node, level, msg) = MPC_LOGS[self.mpc_log_count % MPC_LOGS.len()];
+        add_source(&mut self.log_sources, node);
+        push_log(&mut self.mpc_logs, LogEntry::new(level, node, msg));
+        self.mpc_log_count += 1;
+    }
+}
+
+/// Returns whether the `index`th source is out at `tick` while outages are
+/// simulated, the outages of the sources are staggered.
+fn simulated_outage(tick: u64, index: u64) -> bool {
+    (tick + OUTAGE_PERIOD - index * OUTAGE_DURATION) % OUTAGE_PERIOD < OUTAGE_DURATION
+}
+
+/// Appends the next points of `signal` to `data` and drops the points before
+/// the window `start`.
+fn advance(data: &mut Vec<(f64, f64)>, signal: &mut SinSignal, start: f64) {
+    data.extend(signal.by_ref().take(10));
+    data.retain(|(x, _)| *x >= start);
+}
+
+/// Replaces `data` with the `points` from the window `start` on, at least the
+/// last one, and continues `signal` after them.
+fn restore_series(
+    signal: &mut SinSignal,
+    data: &mut Vec<(f64, f64)>,
+    points: &[(f64, f64)],
+    start: f64,
+) {
+    data.clear();
+    data.extend(points.iter().filter(|(x, _)| *x >= start));
+    if data.is_empty() {
+        data.extend(points.last());
+    }
+    if let Some((x, _)) = data.last() {
+        signal.x = x + signal.interval;
+    }
+}
+
+fn push_log(logs: &mut Vec<LogEntry>, entry: LogEntry) {
+    logs.push(entry);
+    if logs.len() > LOG_HISTORY {
+        logs.drain(..logs.len() - LOG_HISTORY);
+    }
+}
+
+/// Adds `source` to the `sources` seen so far unless it is known already.
+fn add_source(sources: &mut Vec<String>, source: &str) {
+    if !sources.iter().any(|known| known == source) {
+        sources.push(source.to_string());
+    }
+}
+
+fn restore_logs(logs: &mut Vec<LogEntry>, entries: Vec<LogEntry>) {
+    if !entries.is_empty() {
+        let skip = entries.len().saturating_sub(LOG_HISTORY);
+        *logs = entries.into_iter().skip(skip).collect();
+    }
+}
@@ -0,0 +1,165 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect, Size},
+    style::Style,
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Cell, List, ListItem, Paragraph, Row, StatefulWidget, Table, Widget,
+    },
+};
+use tui_scrollview::{ScrollView, ScrollViewState};
+
+use crate::{
+    AppState,
+    alerts::{AlertEventKind, AlertStatus},
+    pages::Page,
+};
+
+pub struct Alerts {
+    pub scroll_view_state: ScrollViewState,
+}
+
+impl Alerts {
+    pub fn new() -> Self {
+        Self {
+            scroll_view_state: ScrollViewState::default(),
+        }
+    }
+}
+
+impl Page for Alerts {
+    fn title(&self) -> &'static str {
+        "Alerts"
+    }
+
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    fn scroll_view_state(&mut self) -> Option<&mut ScrollViewState> {
+        Some(&mut self.scroll_view_state)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &AppState) {
+        let engine = &state.alert_engine;
+        let theme = state.theme();
+        let rules_height = engine.rules.len() as u16 + 4;
+        let history_height = engine.history.len().max(1) as u16 + 2;
+        let height = 5 + rules_height + 3 + history_height;
+        let mut scroll_view = ScrollView::new(Size::new(area.width, height))
+            .vertical_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Automatic)
+            .horizontal_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Never);
+        let scroll_view_area = area;
+        let scroll_view_buf = buf;
+        let mut area = *scroll_view.buf().area();
+        if scroll_view_area.height < height {
+            area.width -= 2; // adjust for scrollbar if vertical scrollbar is visible
+        }
+        let buf = scroll_view.buf_mut();
+
+        let [rules_title, rules_area, history_title, history_area] = Layout::vertical([
+            Constraint::Length(5),
+            Constraint::Length(rules_height),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let summary = if engine.rules.is_empty() {
+            "No alert rules configured".to_string()
+        } else {
+            format!("{} of {} rules firing", engine.firing(), engine.rules.len())
+        };
+        let mut title = vec![
+            Line::raw(""),
+            Line::styled("Alert Rules", Style::new().bold()),
+            Line::raw(summary),
+        ];
+        if let Some(err) = &engine.delivery_error {
+            title.push(Line::styled(
+                format!("Delivery failed: {err}"),
+                Style::new().fg(theme.error),
+            ));
+        }
+        Paragraph::new(title).render(rules_title, buf);
+
+        let header = ["Rule", "Network", "Condition", "Value", "State"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(theme.muted())
+            .bottom_margin(1);
+        let rows = engine.rules.iter().map(|state| {
+            let status = match state.status {
+                AlertStatus::Ok => Span::styled("ok", Style::new().fg(theme.success)),
+                AlertStatus::Pending => Span::styled("pending", Style::new().fg(theme.warning)),
+                AlertStatus::Firing => Span::styled("firing", Style::new().fg(theme.error).bold()),
+            };
+            Row::new(vec![
+                Cell::from(state.rule.name.as_str()),
+                Cell::from(state.network.as_str()),
+                Cell::from(state.rule.to_string()),
+                Cell::from(state.value.map(|v| format!("{v:.2}")).unwrap_or_default()),
+                Cell::from(status),
+            ])
+        });
+        let widths = [
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ];
+        Widget::render(
+            Table::new(rows, widths)
+                .header(header)
+                .block(Block::bordered().border_type(BorderType::Rounded)),
+            rules_area,
+            buf,
+        );
+
+        Paragraph::new(vec![
+            Line::raw(""),
+            Line::styled("History", Style::new().bold()),
+        ])
+        .render(history_title, buf);
+
+        let events: Vec<ListItem> = engine
+            .history
+            .iter()
+            .map(|event| {
+                let kind = match event.kind {
+                    AlertEventKind::Firing => {
+                        Span::styled("FIRING  ", Style::new().fg(theme.error))
+                    }
+                    AlertEventKind::Resolved => {
+                        Span::styled("RESOLVED", Style::new().fg(theme.success))
+                    }
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        event.at.format("%Y/%m/%d %H:%M:%S ").to_string(),
+                        theme.muted(),
+                    ),
+                    kind,
+                    Span::styled(
+                        format!(" {} [{}]", event.rule, event.network),
+                        Style::new().bold(),
+                    ),
+                    Span::raw(format!(": {} (value {:.2})", event.condition, event.value)),
+                ]))
+            })
+            .collect();
+        Widget::render(
+            List::new(events).block(Block::bordered().border_type(BorderType::Rounded)),
+            history_area,
+            buf,
+        );
+
+        scroll_view.render(
+            scroll_view_area,
+            scroll_view_buf,
+            &mut self.scroll_view_state,
+        );
+    }
+}
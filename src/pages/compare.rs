@@ -0,0 +1,176 @@
+use num_format::{Locale, ToFormattedString as _};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseEvent, MouseEventKind},
+    layout::{Constraint, Layout, Position, Rect, Size},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Cell, Paragraph, Row, StatefulWidget, Table, Widget},
+};
+use tui_scrollview::{ScrollView, ScrollViewState};
+
+use crate::{
+    AppState,
+    pages::{Page, content_position},
+    source::{SourceKind, format_age},
+    widgets::{tooltip::Tooltip, txs_per_second::TxsPerSecond},
+};
+
+pub struct Compare {
+    pub scroll_view_state: ScrollViewState,
+    /// Screen area of the scroll view, used to translate mouse events
+    area: Rect,
+    /// Mouse position in the scroll view content
+    hover: Option<Position>,
+}
+
+impl Compare {
+    pub fn new() -> Self {
+        Self {
+            scroll_view_state: ScrollViewState::default(),
+            area: Rect::default(),
+            hover: None,
+        }
+    }
+}
+
+impl Page for Compare {
+    fn title(&self) -> &'static str {
+        "Compare"
+    }
+
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    fn scroll_view_state(&mut self) -> Option<&mut ScrollViewState> {
+        Some(&mut self.scroll_view_state)
+    }
+
+    /// Handles `mouse` over the page, returns whether it was consumed.
+    fn handle_mouse(&mut self, mouse: MouseEvent, _state: &AppState) -> bool {
+        self.hover = content_position(self.area, &self.scroll_view_state, &mouse);
+        self.hover.is_some() && mouse.kind == MouseEventKind::Moved
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &AppState) {
+        let networks = &state.networks;
+        let theme = state.theme();
+        let table_height = networks.len() as u16 + 4;
+        let height = 4 + 15 + table_height;
+        let mut scroll_view = ScrollView::new(Size::new(area.width, height))
+            .vertical_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Automatic)
+            .horizontal_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Never);
+        let scroll_view_area = area;
+        let scroll_view_buf = buf;
+        self.area = scroll_view_area;
+        let mut area = *scroll_view.buf().area();
+        if scroll_view_area.height < height {
+            area.width -= 2; // adjust for scrollbar if vertical scrollbar is visible
+        }
+        let buf = scroll_view.buf_mut();
+
+        let [title_area, txs_area, table_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(15),
+            Constraint::Length(table_height),
+        ])
+        .areas(area);
+
+        let names = networks
+            .iter()
+            .map(|network| network.name.as_str())
+            .collect::<Vec<_>>();
+        Paragraph::new(vec![
+            Line::raw(""),
+            Line::styled("Network Comparison", Style::new().bold()),
+            Line::raw(names.join(" vs ")),
+        ])
+        .render(title_area, buf);
+
+        // the windows of the networks advance independently, so every series
+        // is plotted relative to the end of its own window
+        let relative = networks
+            .iter()
+            .map(|network| {
+                network
+                    .txs_per_second_data
+                    .iter()
+                    .map(|(x, y)| (x - network.window[1], *y))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let width = networks
+            .iter()
+            .map(|network| network.window[1] - network.window[0])
+            .fold(0.0, f64::max);
+        let series = networks
+            .iter()
+            .zip(&relative)
+            .map(|(network, data)| (network.name.as_str(), data.as_slice()))
+            .collect();
+        let txs_per_second = TxsPerSecond::compare([-width, 0.0], series, theme);
+        let tooltip = self.hover.and_then(|position| {
+            txs_per_second
+                .tooltip(txs_area, position)
+                .map(|lines| Tooltip::new(lines, position))
+        });
+        txs_per_second.render(txs_area, buf);
+
+        let header = [
+            "Network",
+            "Transactions",
+            "Utilization",
+            "Total transactions",
+            "Avg. latency",
+            "Avg. coSNARK time",
+            "Updated",
+        ]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(theme.muted())
+        .bottom_margin(1);
+        let rows = networks.iter().enumerate().map(|(i, network)| {
+            let tps = network
+                .txs_per_second_data
+                .last()
+                .copied()
+                .unwrap_or_default()
+                .1;
+            let metrics = network.source(SourceKind::Metrics);
+            let row = Row::new(vec![
+                Cell::from(theme.series_label(i, &network.name)).style(theme.series(i).bold()),
+                Cell::from(format!("{tps:.2} tx/s")),
+                Cell::from(format!("{:.0} %", network.network_util * 100.0)),
+                Cell::from(network.total_txs.to_formatted_string(&Locale::en)),
+                Cell::from(format!("{:.1} sec/tx", network.avg_tx_latency)),
+                Cell::from(format!("{:.1} sec/tx", network.avg_proof_time)),
+                Cell::from(metrics.age().map(format_age).unwrap_or_default()),
+            ]);
+            if metrics.is_stale() {
+                row.style(theme.muted())
+            } else {
+                row
+            }
+        });
+        let widths = [Constraint::Fill(1); 7];
+        Widget::render(
+            Table::new(rows, widths)
+                .header(header)
+                .block(Block::bordered().border_type(BorderType::Rounded)),
+            table_area,
+            buf,
+        );
+
+        if let Some(tooltip) = tooltip {
+            tooltip.render(area, buf);
+        }
+
+        scroll_view.render(
+            scroll_view_area,
+            scroll_view_buf,
+            &mut self.scroll_view_state,
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use chrono::TimeDelta;
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Rect, Size},
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    layout::{Constraint, Position, Rect, Size},
     style::Style,
     text::Line,
     widgets::{Paragraph, StatefulWidget, Widget},
@@ -8,142 +10,212 @@ use ratatui::{
 use tui_scrollview::{ScrollView, ScrollViewState};
 
 use crate::{
-    LARGE_MIN, SinSignal,
+    AppState, LARGE_MIN,
+    export::{self, ExportFormat},
+    keymap::{Action, Context},
     layout::{LayoutBuilder, Node},
+    pages::{Page, content_position},
+    source::{SourceKind, format_age},
     widgets::{
-        avg_proof_time::AvgProofTime, avg_tx_latency::AvgTxLatency, mpc_log::MpcLog,
-        network_util::NetworkUtil, node_util::NodeUtil, total_txs::TotalTxs, txs_log::TxsLog,
+        avg_proof_time::AvgProofTime, avg_tx_latency::AvgTxLatency, command_palette::CommandKind,
+        freshness::Freshness, log_view::LogViewState, mpc_log::MpcLog, network_util::NetworkUtil,
+        node_util::NodeUtil, tooltip::Tooltip, total_txs::TotalTxs, txs_log::TxsLog,
         txs_per_second::TxsPerSecond,
     },
 };
 
-const TXS_LOGS: [(&str, &str); 13] = [
-    ("block-0", "50 transactions published in 0x1234...abcd"),
-    ("block-1", "50 transactions published in 0x1234...abcd"),
-    ("block-2", "50 transactions published in 0x1234...abcd"),
-    ("block-3", "50 transactions published in 0x1234...abcd"),
-    ("block-4", "50 transactions published in 0x1234...abcd"),
-    ("block-5", "50 transactions published in 0x1234...abcd"),
-    ("block-6", "50 transactions published in 0x1234...abcd"),
-    ("block-7", "50 transactions published in 0x1234...abcd"),
-    ("block-8", "50 transactions published in 0x1234...abcd"),
-    ("block-9", "50 transactions published in 0x1234...abcd"),
-    ("block-10", "50 transactions published in 0x1234...abcd"),
-    ("block-11", "50 transactions published in 0x1234...abcd"),
-    ("block-12", "50 transactions published in 0x1234...abcd"),
-];
-
-const MPC_LOGS: [(&str, &str); 13] = [
-    ("MPC Coordinator", "Sending job to MPC nodes"),
-    (
-        "MPC Node 1",
-        "Received MPC job for a batch of transactions of size 50",
-    ),
-    (
-        "MPC Node 2",
-        "Received MPC job for a batch of transactions of size 50",
-    ),
-    (
-        "MPC Node 3",
-        "Received MPC job for a batch of transactions of size 50",
-    ),
-    ("MPC Node 1", "Finished connection establishment in 15ms"),
-    ("MPC Node 2", "Finished connection establishment in 15ms"),
-    ("MPC Node 3", "Finished connection establishment in 15ms"),
-    ("MPC Node 1", "Start processing job.."),
-    ("MPC Node 2", "Start processing job.."),
-    ("MPC Node 3", "Start processing job.."),
-    ("MPC Node 1", "Finished processing job in 500ms"),
-    ("MPC Node 2", "Finished processing job in 500ms"),
-    ("MPC Node 3", "Finished processing job in 500ms"),
-];
-
 pub struct Dashboard {
     pub scroll_view_state: ScrollViewState,
-    window: [f64; 2],
-    txs_per_second_signal: SinSignal,
-    txs_per_second_data: Vec<(f64, f64)>,
-    network_util: f64,
-    node_cpu_signal: SinSignal,
-    node_cpu_data: Vec<(f64, f64)>,
-    node_net_up_signal: SinSignal,
-    node_net_up_data: Vec<(f64, f64)>,
-    node_net_down_signal: SinSignal,
-    node_net_down_data: Vec<(f64, f64)>,
-    tx_logs: Vec<(String, String)>,
-    mpc_logs: Vec<(String, String)>,
+    pub tx_log_state: LogViewState,
+    pub mpc_log_state: LogViewState,
+    /// Screen area of the scroll view, used to translate mouse events
+    area: Rect,
+    /// Mouse position in the scroll view content
+    hover: Option<Position>,
+    /// Outcome of the last export
+    notice: Option<Result<String, String>>,
+    /// Time range shown by the charts, the live window if unset
+    chart_range: Option<TimeDelta>,
 }
 
 impl Dashboard {
     pub fn new() -> Self {
-        let mut txs_per_second_signal = SinSignal::new(0.1, 2.0, 10.0);
-        let txs_per_second_data = txs_per_second_signal
-            .by_ref()
-            .take(200)
-            .collect::<Vec<(f64, f64)>>();
-        let mut node_cpu_signal = SinSignal::new(0.1, 3.0, 10.0);
-        let node_cpu_data = node_cpu_signal
-            .by_ref()
-            .take(200)
-            .collect::<Vec<(f64, f64)>>();
-        let mut node_net_up_signal = SinSignal::new(0.1, 1.0, 10.0);
-        let node_net_up_data = node_net_up_signal
-            .by_ref()
-            .take(200)
-            .collect::<Vec<(f64, f64)>>();
-        let mut node_net_down_signal = SinSignal::new(0.1, 2.5, 10.0);
-        let node_net_down_data = node_net_down_signal
-            .by_ref()
-            .take(200)
-            .collect::<Vec<(f64, f64)>>();
-
         Self {
             scroll_view_state: ScrollViewState::default(),
-            window: [0.0, 20.0],
-            txs_per_second_signal,
-            txs_per_second_data,
-            network_util: 0.0,
-            node_cpu_signal,
-            node_cpu_data,
-            node_net_up_signal,
-            node_net_up_data,
-            node_net_down_signal,
-            node_net_down_data,
-            tx_logs: TXS_LOGS
-                .iter()
-                .map(|e| (e.0.to_string(), e.1.to_string()))
-                .collect(),
-            mpc_logs: MPC_LOGS
-                .iter()
-                .map(|e| (e.0.to_string(), e.1.to_string()))
-                .collect(),
+            tx_log_state: LogViewState::default(),
+            mpc_log_state: LogViewState::default(),
+            area: Rect::default(),
+            hover: None,
+            notice: None,
+            chart_range: None,
+        }
+    }
+
+    /// Exports the visible range of the charts of the selected network.
+    fn export(&mut self, state: &AppState, format: ExportFormat) {
+        self.notice = Some(
+            export::charts(state.network(), self.chart_range, format, &state.output_dir)
+                .map(|(path, count)| format!("Exported {count} points to {}", path.display()))
+                .map_err(|err| format!("Export failed: {err:#}")),
+        );
+    }
+
+    /// Moves the focus from the page to the transaction logs, the MPC logs and back.
+    fn cycle_log_focus(&mut self) {
+        let (tx, mpc) = match (self.tx_log_state.focused, self.mpc_log_state.focused) {
+            (false, false) => (true, false),
+            (true, _) => (false, true),
+            (false, true) => (false, false),
+        };
+        self.tx_log_state.focused = tx;
+        self.mpc_log_state.focused = mpc;
+    }
+
+    fn focused_log(&self) -> Option<&LogViewState> {
+        [&self.tx_log_state, &self.mpc_log_state]
+            .into_iter()
+            .find(|state| state.focused)
+    }
+}
+
+impl Page for Dashboard {
+    fn title(&self) -> &'static str {
+        "Dashboard"
+    }
+
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    fn scroll_view_state(&mut self) -> Option<&mut ScrollViewState> {
+        Some(&mut self.scroll_view_state)
+    }
+
+    /// Returns the key binding context of the focused log panel.
+    fn context(&self) -> Context {
+        match self.focused_log() {
+            Some(state) if state.filter_focused => Context::Search,
+            Some(_) => Context::Table,
+            None => Context::Global,
+        }
+    }
+
+    /// Returns the actions the page handles in its current context.
+    fn actions(&self) -> &'static [Action] {
+        match self.context() {
+            Context::Global => &[Action::CycleFocus, Action::Export],
+            Context::Table => &[
+                Action::ScrollDown,
+                Action::ScrollUp,
+                Action::Top,
+                Action::Bottom,
+                Action::TogglePause,
+                Action::FocusSearch,
+                Action::CycleFocus,
+                Action::Unfocus,
+            ],
+            Context::Search => &[Action::Submit, Action::Unfocus],
+        }
+    }
+
+    fn palette_actions(&self) -> &'static [Action] {
+        &[
+            Action::Export,
+            Action::CycleFocus,
+            Action::TogglePause,
+            Action::Top,
+            Action::Bottom,
+            Action::FocusSearch,
+        ]
+    }
+
+    /// Exports the charts whatever is focused, the actions of the log panels
+    /// apply to the focused panel or to the transaction logs.
+    fn run_palette_action(&mut self, action: Action, state: &AppState) -> bool {
+        match action {
+            Action::Export => {
+                self.export(state, state.export_format);
+                true
+            }
+            Action::CycleFocus => self.handle_action(action, state),
+            _ => {
+                if self.focused_log().is_none() {
+                    self.tx_log_state.focused = true;
+                }
+                self.tx_log_state.filter_focused = false;
+                self.mpc_log_state.filter_focused = false;
+                self.handle_action(action, state)
+            }
         }
     }
 
-    pub fn on_tick(&mut self) {
-        self.window[0] += 1.0;
-        self.window[1] += 1.0;
+    /// Handles `action` on the page, returns whether it was consumed.
+    fn handle_action(&mut self, action: Action, state: &AppState) -> bool {
+        let network = state.network();
+        if action == Action::CycleFocus && self.context() != Context::Search {
+            self.cycle_log_focus();
+            true
+        } else if action == Action::Export && self.context() == Context::Global {
+            self.export(state, state.export_format);
+            true
+        } else if self.tx_log_state.focused {
+            self.tx_log_state.handle_action(action, &network.tx_logs)
+        } else if self.mpc_log_state.focused {
+            self.mpc_log_state.handle_action(action, &network.mpc_logs)
+        } else {
+            false
+        }
+    }
 
-        self.txs_per_second_data.drain(0..10);
-        self.txs_per_second_data
-            .extend(self.txs_per_second_signal.by_ref().take(10));
-        self.network_util =
-            ((self.txs_per_second_data.last().unwrap().1 + 10.0) / 20.0).clamp(0.0, 1.0);
-        self.node_cpu_data.drain(0..10);
-        self.node_cpu_data
-            .extend(self.node_cpu_signal.by_ref().take(10));
-        self.node_net_up_data.drain(0..10);
-        self.node_net_up_data
-            .extend(self.node_net_up_signal.by_ref().take(10));
-        self.node_net_down_data.drain(0..10);
-        self.node_net_down_data
-            .extend(self.node_net_down_signal.by_ref().take(10));
+    /// Types `key` into the filter bar of the focused log panel.
+    fn handle_input(&mut self, key: KeyEvent) {
+        if self.tx_log_state.focused {
+            self.tx_log_state.handle_input(key);
+        } else if self.mpc_log_state.focused {
+            self.mpc_log_state.handle_input(key);
+        }
+    }
+
+    /// Handles `mouse` over the page, returns whether it was consumed.
+    ///
+    /// Moving the mouse over a chart shows a tooltip, scrolling and clicking
+    /// over a log panel is forwarded to it.
+    fn handle_mouse(&mut self, mouse: MouseEvent, state: &AppState) -> bool {
+        let network = state.network();
+        let Some(position) = content_position(self.area, &self.scroll_view_state, &mouse) else {
+            self.hover = None;
+            return false;
+        };
+        if mouse.kind == MouseEventKind::Moved {
+            self.hover = Some(position);
+            return true;
+        }
+        let clicked = mouse.kind == MouseEventKind::Down(MouseButton::Left);
+        if self
+            .tx_log_state
+            .handle_mouse(mouse.kind, position, &network.tx_logs)
+        {
+            self.mpc_log_state.focused &= !clicked;
+            true
+        } else if self
+            .mpc_log_state
+            .handle_mouse(mouse.kind, position, &network.mpc_logs)
+        {
+            self.tx_log_state.focused &= !clicked;
+            true
+        } else {
+            false
+        }
+    }
 
-        self.tx_logs.rotate_left(1);
-        self.mpc_logs.rotate_left(1);
+    fn on_network_change(&mut self, previous: usize, current: usize) {
+        self.tx_log_state.on_network_change(previous, current);
+        self.mpc_log_state.on_network_change(previous, current);
     }
 
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &AppState) {
+        let network = state.network();
+        let theme = state.theme();
         let height = if area.width >= LARGE_MIN {
             15 + 4 + 30 + 3 + 20
         } else {
@@ -154,6 +226,7 @@ impl Dashboard {
             .horizontal_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Never);
         let scroll_view_area = area;
         let scroll_view_buf = buf;
+        self.area = scroll_view_area;
         let mut area = *scroll_view.buf().area();
         if scroll_view_area.height < height {
             area.width -= 2; // adjust for scrollbar if vertical scrollbar is visible
@@ -222,28 +295,48 @@ impl Dashboard {
                 .build(area)
         };
 
-        TxsPerSecond::new(self.window, &self.txs_per_second_data).render(layout["txs_graph"], buf);
+        let (window, [tps, cpu, up, down]) = network.charts(self.chart_range);
+        let txs_per_second = TxsPerSecond::new(window, &tps, theme);
+        let node_util = NodeUtil::new(window, &cpu, &up, &down, theme);
+        let tooltip = self.hover.and_then(|position| {
+            txs_per_second
+                .tooltip(layout["txs_graph"], position)
+                .or_else(|| node_util.tooltip(layout["net_stats_graph"], position))
+                .map(|lines| Tooltip::new(lines, position))
+        });
+
+        let metrics = network.source(SourceKind::Metrics);
+        Freshness::new(txs_per_second, metrics, theme).render(layout["txs_graph"], buf);
 
         Paragraph::new(vec![
             Line::raw(""),
             Line::styled("Network Stats", Style::new().bold()),
-            Line::raw("Updated less than 10 seconds ago"),
+            Line::raw(match self.chart_range {
+                Some(range) => format!(
+                    "Metrics {}, charts of the last {}",
+                    metrics.updated_ago(),
+                    format_age(range)
+                ),
+                None => format!("Metrics {}", metrics.updated_ago()),
+            }),
         ])
         .render(layout["net_stats_title"], buf);
 
-        NetworkUtil::new(self.network_util).render(layout["net_stats_gauge"], buf);
-
-        NodeUtil::new(
-            self.window,
-            &self.node_cpu_data,
-            &self.node_net_up_data,
-            &self.node_net_down_data,
+        Freshness::new(
+            NetworkUtil::new(network.network_util, theme),
+            metrics,
+            theme,
         )
-        .render(layout["net_stats_graph"], buf);
+        .render(layout["net_stats_gauge"], buf);
+
+        Freshness::new(node_util, metrics, theme).render(layout["net_stats_graph"], buf);
 
-        TotalTxs::new(1_000_000).render(layout["net_stats_values_0"], buf);
-        AvgTxLatency::new(200.0).render(layout["net_stats_values_1"], buf);
-        AvgProofTime::new(5.2).render(layout["net_stats_values_2"], buf);
+        Freshness::new(TotalTxs::new(network.total_txs), metrics, theme)
+            .render(layout["net_stats_values_0"], buf);
+        Freshness::new(AvgTxLatency::new(network.avg_tx_latency), metrics, theme)
+            .render(layout["net_stats_values_1"], buf);
+        Freshness::new(AvgProofTime::new(network.avg_proof_time), metrics, theme)
+            .render(layout["net_stats_values_2"], buf);
 
         Paragraph::new(vec![
             Line::raw(""),
@@ -251,8 +344,22 @@ impl Dashboard {
         ])
         .render(layout["live_logs_title"], buf);
 
-        TxsLog::new(&self.tx_logs).render(layout["txs_logs"], buf);
-        MpcLog::new(&self.mpc_logs).render(layout["mpc_logs"], buf);
+        Freshness::new(
+            TxsLog::new(&network.tx_logs, theme),
+            network.source(SourceKind::Blocks),
+            theme,
+        )
+        .render(layout["txs_logs"], buf, &mut self.tx_log_state);
+        Freshness::new(
+            MpcLog::new(&network.mpc_logs, &network.log_sources, theme),
+            network.source(SourceKind::MpcLogs),
+            theme,
+        )
+        .render(layout["mpc_logs"], buf, &mut self.mpc_log_state);
+
+        if let Some(tooltip) = tooltip {
+            tooltip.render(area, buf);
+        }
 
         scroll_view.render(
             scroll_view_area,
@@ -260,4 +367,17 @@ impl Dashboard {
             &mut self.scroll_view_state,
         );
     }
+
+    fn run_command(&mut self, command: &CommandKind, state: &AppState) -> bool {
+        match command {
+            CommandKind::Export(format) => self.export(state, *format),
+            CommandKind::ChartRange(range) => self.chart_range = *range,
+            _ => return false,
+        }
+        true
+    }
+
+    fn take_notice(&mut self) -> Option<Result<String, String>> {
+        self.notice.take()
+    }
 }
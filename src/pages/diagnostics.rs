@@ -0,0 +1,207 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect, Size},
+    style::Style,
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Cell, List, ListItem, Paragraph, Row, StatefulWidget, Table, Widget,
+    },
+};
+use tracing::Level;
+use tui_scrollview::{ScrollView, ScrollViewState};
+
+use crate::{
+    AppState,
+    pages::Page,
+    source::{Connection, SourceKind, format_age},
+    theme::Theme,
+};
+
+pub struct Diagnostics {
+    pub scroll_view_state: ScrollViewState,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self {
+            scroll_view_state: ScrollViewState::default(),
+        }
+    }
+}
+
+impl Page for Diagnostics {
+    fn title(&self) -> &'static str {
+        "Diagnostics"
+    }
+
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    fn scroll_view_state(&mut self) -> Option<&mut ScrollViewState> {
+        Some(&mut self.scroll_view_state)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &AppState) {
+        let theme = state.theme();
+        let events = state.diagnostics.events();
+        let sources_height = (state.networks.len() * SourceKind::ALL.len()) as u16 + 4;
+        let events_height = events.len().max(1) as u16 + 2;
+        let height = 5 + sources_height + 3 + events_height;
+        let mut scroll_view = ScrollView::new(Size::new(area.width, height))
+            .vertical_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Automatic)
+            .horizontal_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Never);
+        let scroll_view_area = area;
+        let scroll_view_buf = buf;
+        let mut area = *scroll_view.buf().area();
+        if scroll_view_area.height < height {
+            area.width -= 2; // adjust for scrollbar if vertical scrollbar is visible
+        }
+        let buf = scroll_view.buf_mut();
+
+        let [sources_title, sources_area, events_title, events_area] = Layout::vertical([
+            Constraint::Length(5),
+            Constraint::Length(sources_height),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let mut title = vec![
+            Line::raw(""),
+            Line::styled("Sources", Style::new().bold()),
+            match &state.diagnostics.log_error {
+                Some(err) => Line::styled(
+                    format!("Logging to files disabled: {err}"),
+                    Style::new().fg(theme.error),
+                ),
+                None => Line::raw(format!(
+                    "Log files in {}",
+                    state.diagnostics.log_dir.display()
+                )),
+            },
+        ];
+        if let Some(err) = &state.alert_engine.delivery_error {
+            title.push(Line::styled(
+                format!("Alert delivery failed: {err}"),
+                Style::new().fg(theme.error),
+            ));
+        }
+        Paragraph::new(title).render(sources_title, buf);
+
+        let header = [
+            "Network",
+            "Source",
+            "Connection",
+            "History",
+            "Updates",
+            "Last update",
+            "Error",
+        ]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(theme.muted())
+        .bottom_margin(1);
+        let rows = state.networks.iter().flat_map(|network| {
+            network.sources.iter().map(|source| {
+                let color = match source.connection {
+                    Connection::Connected => theme.success,
+                    Connection::Reconnecting => theme.warning,
+                    Connection::Failed => theme.error,
+                };
+                let updated = Span::styled(
+                    source.updated_ago(),
+                    if source.is_stale() {
+                        Style::new().fg(theme.warning)
+                    } else {
+                        Style::new()
+                    },
+                );
+                let connection = match source.retry_in() {
+                    Some(retry_in) if source.connection != Connection::Connected => format!(
+                        "{} ({}×), retry in {}",
+                        source.connection.name(),
+                        source.failures,
+                        format_age(retry_in)
+                    ),
+                    _ => source.connection.name().to_string(),
+                };
+                let history = match (&network.history_error, network.has_history()) {
+                    (Some(_), _) => Span::styled("failing", Style::new().fg(theme.error)),
+                    (None, true) => Span::raw("on"),
+                    (None, false) => Span::raw("off"),
+                };
+                let error = source.error.as_deref().or(network.history_error.as_deref());
+                Row::new(vec![
+                    Cell::from(network.name.as_str()),
+                    Cell::from(source.kind.name()),
+                    Cell::from(connection).style(Style::new().fg(color)),
+                    Cell::from(history),
+                    Cell::from(source.updates.to_string()),
+                    Cell::from(updated),
+                    Cell::from(error.unwrap_or_default()).style(Style::new().fg(theme.error)),
+                ])
+            })
+        });
+        Widget::render(
+            Table::new(
+                rows,
+                [
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(2),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(3),
+                ],
+            )
+            .header(header)
+            .block(Block::bordered().border_type(BorderType::Rounded)),
+            sources_area,
+            buf,
+        );
+
+        Paragraph::new(vec![
+            Line::raw(""),
+            Line::styled("Internal Events", Style::new().bold()),
+        ])
+        .render(events_title, buf);
+
+        let events: Vec<ListItem> = events
+            .iter()
+            .map(|event| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(event.at.format("%H:%M:%S%.3f ").to_string(), theme.muted()),
+                    Span::styled(
+                        format!("{:<5} ", event.level),
+                        level_style(event.level, theme),
+                    ),
+                    Span::styled(format!("{} ", event.target), theme.muted()),
+                    Span::raw(event.message.as_str()),
+                ]))
+            })
+            .collect();
+        Widget::render(
+            List::new(events).block(Block::bordered().border_type(BorderType::Rounded)),
+            events_area,
+            buf,
+        );
+
+        scroll_view.render(
+            scroll_view_area,
+            scroll_view_buf,
+            &mut self.scroll_view_state,
+        );
+    }
+}
+
+fn level_style(level: Level, theme: &Theme) -> Style {
+    match level {
+        Level::ERROR => Style::new().fg(theme.error).bold(),
+        Level::WARN => Style::new().fg(theme.warning).bold(),
+        Level::INFO => Style::new().fg(theme.success),
+        _ => theme.muted(),
+    }
+}
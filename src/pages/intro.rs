@@ -7,6 +7,8 @@ use ratatui::{
 };
 use tui_scrollview::{ScrollView, ScrollViewState};
 
+use crate::{AppState, pages::Page};
+
 pub struct Intro {
     pub scroll_view_state: ScrollViewState,
 }
@@ -17,10 +19,22 @@ impl Intro {
             scroll_view_state: ScrollViewState::default(),
         }
     }
+}
+
+impl Page for Intro {
+    fn title(&self) -> &'static str {
+        "Introduction"
+    }
 
-    pub fn on_tick(&mut self) {}
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    fn scroll_view_state(&mut self) -> Option<&mut ScrollViewState> {
+        Some(&mut self.scroll_view_state)
+    }
 
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+    fn render(&mut self, area: Rect, buf: &mut Buffer, _state: &AppState) {
         {
             let height = 20;
             let mut scroll_view = ScrollView::new(Size::new(area.width, height))
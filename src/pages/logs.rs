@@ -0,0 +1,765 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write as _},
+    mem,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local, NaiveTime, TimeDelta};
+use eyre::Context as _;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Cell, Paragraph, Row, StatefulWidget, Table, Widget},
+};
+use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
+
+use crate::{
+    AppState, export,
+    keymap::{Action, Context},
+    logs::{LogEntry, LogFilter},
+    network::Network,
+    pages::Page,
+    source::SourceKind,
+    widgets::{
+        command_palette::CommandKind,
+        log_view::{level_style, source_style},
+        text_input::{TextInput, TextInputState},
+    },
+};
+
+const TIME_WIDTH: u16 = 8;
+const SOURCE_WIDTH: u16 = 16;
+const LEVEL_WIDTH: u16 = 5;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Input {
+    None,
+    Filter,
+    Time,
+}
+
+/// Pause, scroll position and selection in the logs of one network.
+#[derive(Default)]
+struct View {
+    paused: Option<Vec<LogEntry>>,
+    /// Number of entries scrolled back from the newest one, the page is
+    /// paused while scrolled back so that it stays on the same entries
+    offset: usize,
+    /// Entry to show at the top on the next render, set by jump-to-time
+    jump_to: Option<usize>,
+    /// Index of the clicked entry in the filtered entries
+    selected: Option<usize>,
+}
+
+/// All log streams of a network merged in timestamp order.
+pub struct Logs {
+    input: Input,
+    filter_state: TextInputState,
+    filter_query: String,
+    filter: LogFilter,
+    time_state: TextInputState,
+    wrap: bool,
+    /// Position in the logs of the selected network
+    view: View,
+    /// Positions in the logs of the other networks, by network index
+    views: HashMap<usize, View>,
+    /// Outcome of the last export or jump, shown below the title
+    status: Option<Result<String, String>>,
+    table_area: Rect,
+    /// Index of the first visible entry and the rows taken by each visible entry
+    visible_rows: (usize, Vec<Range<u16>>),
+}
+
+impl Logs {
+    pub fn new() -> Self {
+        Self {
+            input: Input::None,
+            filter_state: TextInputState::default().hint_text("node:<name> level:<level> <regex>"),
+            filter_query: String::new(),
+            filter: LogFilter::default(),
+            time_state: TextInputState::default().hint_text("HH:MM[:SS]"),
+            wrap: false,
+            view: View::default(),
+            views: HashMap::new(),
+            status: None,
+            table_area: Rect::default(),
+            visible_rows: (0, Vec::new()),
+        }
+    }
+
+    fn toggle_pause(&mut self, network: &Network) {
+        self.view.paused = match self.view.paused {
+            Some(_) => None,
+            None => Some(snapshot(network)),
+        };
+        self.view.selected = None;
+    }
+
+    /// Scrolls back by `lines` entries, pausing the page first.
+    fn scroll_back(&mut self, lines: usize, network: &Network) {
+        if self.view.paused.is_none() {
+            self.toggle_pause(network);
+        }
+        self.view.offset = self.view.offset.saturating_add(lines);
+    }
+
+    fn scroll_forward(&mut self) {
+        self.view.offset = self.view.offset.saturating_sub(1);
+    }
+
+    fn update_filter(&mut self) {
+        if self.filter_state.text != self.filter_query {
+            self.filter_query.clone_from(&self.filter_state.text);
+            self.filter = LogFilter::parse(&self.filter_query);
+            self.view.selected = None;
+        }
+    }
+
+    /// Shows the entries matching `query`, as if typed into the filter bar.
+    fn set_filter(&mut self, query: &str) {
+        self.filter_state.text = query.to_string();
+        self.filter_state.cursor_pos = query.len();
+        self.update_filter();
+        self.view.offset = 0;
+    }
+
+    /// Scrolls to the first entry logged within the last `range`.
+    fn jump_back(&mut self, range: TimeDelta, network: &Network) {
+        self.jump_to_time(Local::now() - range, network);
+    }
+
+    fn jump(&mut self, network: &Network) {
+        let Ok(time) = NaiveTime::parse_from_str(&self.time_state.text, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(&self.time_state.text, "%H:%M"))
+        else {
+            self.status = Some(Err(format!(
+                "Invalid time `{}`, expected HH:MM[:SS]",
+                self.time_state.text
+            )));
+            return;
+        };
+        let now = Local::now();
+        let mut target = now.with_time(time).earliest().unwrap_or(now);
+        if target > now {
+            target -= TimeDelta::days(1);
+        }
+        self.jump_to_time(target, network);
+    }
+
+    /// Scrolls to the first entry logged at or after `target` and pauses the
+    /// page there, fails if the retained entries do not reach back that far.
+    fn jump_to_time(&mut self, target: DateTime<Local>, network: &Network) {
+        let was_paused = self.view.paused.is_some();
+        let snapshot = self.view.paused.take().unwrap_or_else(|| snapshot(network));
+        let entries = filtered(Some(&snapshot), &self.filter, network);
+        let jump = match entries.first() {
+            None => Err("No entries to jump to".to_string()),
+            Some(oldest) if target < oldest.timestamp => Err(format!(
+                "No entries that old, the oldest is from {}",
+                oldest.timestamp.format("%H:%M:%S")
+            )),
+            Some(_) => Ok(entries
+                .partition_point(|entry| entry.timestamp < target)
+                .min(entries.len() - 1)),
+        };
+        match jump {
+            Ok(index) => {
+                self.view.jump_to = Some(index);
+                self.status = None;
+                self.view.paused = Some(snapshot);
+                if !was_paused {
+                    self.view.selected = None;
+                }
+            }
+            Err(msg) => {
+                self.status = Some(Err(msg));
+                if was_paused {
+                    self.view.paused = Some(snapshot);
+                }
+            }
+        }
+    }
+
+    /// Writes the filtered view to a timestamped file in the output `dir`.
+    fn export(&self, network: &Network, dir: &Path) -> eyre::Result<(PathBuf, usize)> {
+        let entries = filtered(self.view.paused.as_deref(), &self.filter, network);
+        let path = export::output_path(
+            dir,
+            &format!(
+                "merces-logs-{}-{}.log",
+                network.name.to_lowercase(),
+                Local::now().format("%Y%m%d-%H%M%S")
+            ),
+        )?;
+        let file =
+            File::create(&path).wrap_err_with(|| format!("cannot create {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        for entry in &entries {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}",
+                entry.timestamp.to_rfc3339(),
+                entry.source,
+                entry.level,
+                entry.message
+            )?;
+        }
+        writer.flush()?;
+        Ok((path, entries.len()))
+    }
+}
+
+impl Page for Logs {
+    fn title(&self) -> &'static str {
+        "Logs"
+    }
+
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    /// Returns the key binding context of the page.
+    fn context(&self) -> Context {
+        match self.input {
+            Input::None => Context::Table,
+            Input::Filter | Input::Time => Context::Search,
+        }
+    }
+
+    /// Returns the actions the page handles in its current context.
+    fn actions(&self) -> &'static [Action] {
+        match self.context() {
+            Context::Search => &[Action::Submit, Action::Unfocus],
+            Context::Global | Context::Table => &[
+                Action::ScrollDown,
+                Action::ScrollUp,
+                Action::Top,
+                Action::Bottom,
+                Action::FocusSearch,
+                Action::JumpToTime,
+                Action::TogglePause,
+                Action::ToggleWrap,
+                Action::Export,
+            ],
+        }
+    }
+
+    fn palette_actions(&self) -> &'static [Action] {
+        &[
+            Action::TogglePause,
+            Action::Top,
+            Action::Bottom,
+            Action::FocusSearch,
+            Action::JumpToTime,
+            Action::ToggleWrap,
+            Action::Export,
+        ]
+    }
+
+    /// Leaves the focused input and handles `action`.
+    fn run_palette_action(&mut self, action: Action, state: &AppState) -> bool {
+        self.input = Input::None;
+        self.handle_action(action, state)
+    }
+
+    /// Handles `action` on the page, returns whether it was consumed.
+    fn handle_action(&mut self, action: Action, state: &AppState) -> bool {
+        let network = state.network();
+        match (self.input, action) {
+            (Input::Time, Action::Submit) => {
+                self.input = Input::None;
+                self.jump(network);
+            }
+            (Input::Filter | Input::Time, Action::Unfocus | Action::Submit) => {
+                self.input = Input::None;
+            }
+            // the arrow keys must not scroll the page while typing
+            (Input::Filter | Input::Time, Action::ScrollUp | Action::ScrollDown) => {}
+            (Input::Filter | Input::Time, _) => return false,
+            (Input::None, Action::ScrollDown) => self.scroll_forward(),
+            (Input::None, Action::ScrollUp) => self.scroll_back(1, network),
+            (Input::None, Action::Top) => self.scroll_back(usize::MAX, network),
+            (Input::None, Action::Bottom) => {
+                self.view.offset = 0;
+                self.view.paused = None;
+                self.view.selected = None;
+            }
+            (Input::None, Action::FocusSearch) => self.input = Input::Filter,
+            (Input::None, Action::JumpToTime) => self.input = Input::Time,
+            (Input::None, Action::ToggleWrap) => self.wrap = !self.wrap,
+            (Input::None, Action::TogglePause) => self.toggle_pause(network),
+            (Input::None, Action::Export) => {
+                self.status = Some(
+                    self.export(network, &state.output_dir)
+                        .map(|(path, count)| {
+                            format!("Exported {count} entries to {}", path.display())
+                        })
+                        .map_err(|err| format!("Export failed: {err:#}")),
+                );
+            }
+            (Input::None, _) => return false,
+        }
+        true
+    }
+
+    /// Types `key` into the focused input.
+    fn handle_input(&mut self, key: KeyEvent) {
+        match self.input {
+            Input::Filter => {
+                self.filter_state.handle_events(key.code, key.modifiers);
+                self.update_filter();
+                self.view.offset = 0;
+            }
+            Input::Time => self.time_state.handle_events(key.code, key.modifiers),
+            Input::None => {}
+        }
+    }
+
+    /// Handles `mouse` over the log table, returns whether it was consumed.
+    ///
+    /// Clicking an entry pauses the page and selects the entry.
+    fn handle_mouse(&mut self, mouse: MouseEvent, state: &AppState) -> bool {
+        let network = state.network();
+        let position = Position::new(mouse.column, mouse.row);
+        if !self.table_area.contains(position) {
+            return false;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll_forward(),
+            MouseEventKind::ScrollUp => self.scroll_back(1, network),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let (start, rows) = &self.visible_rows;
+                let Some(index) = rows.iter().position(|rows| rows.contains(&position.y)) else {
+                    return false;
+                };
+                let index = start + index;
+                if self.view.paused.is_none() {
+                    self.toggle_pause(network);
+                }
+                self.view.selected = Some(index);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &AppState) {
+        let network = state.network();
+        let theme = state.theme();
+        let [title_area, inputs_area, table_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [filter_area, time_area] =
+            Layout::horizontal([Constraint::Max(60), Constraint::Length(22)])
+                .spacing(1)
+                .areas(inputs_area);
+
+        let entries = filtered(self.view.paused.as_deref(), &self.filter, network);
+        let total = match &self.view.paused {
+            Some(entries) => entries.len(),
+            None => network.tx_logs.len() + network.mpc_logs.len(),
+        };
+
+        let mut status = vec![Span::raw(format!(
+            "{} of {total} entries of {}",
+            entries.len(),
+            network.name
+        ))];
+        if self.view.paused.is_some() {
+            status.push(Span::styled(" [paused]", Style::new().fg(theme.warning)));
+        }
+        for kind in [SourceKind::Blocks, SourceKind::MpcLogs] {
+            let source = network.source(kind);
+            if source.is_stale() {
+                status.push(Span::styled(
+                    format!(" [{} stale, {}]", kind.name(), source.updated_ago()),
+                    Style::new().fg(theme.warning),
+                ));
+            }
+        }
+        status.push(Span::styled(
+            if self.wrap { " [wrap]" } else { " [truncate]" },
+            theme.muted(),
+        ));
+        if let Some(err) = &self.filter.error {
+            status.push(Span::styled(
+                format!(" invalid filter: {err}"),
+                Style::new().fg(theme.error),
+            ));
+        }
+        let mut title = vec![
+            Line::raw(""),
+            Line::styled("Logs", Style::new().bold()),
+            Line::from(status),
+        ];
+        match &self.status {
+            Some(Ok(msg)) => title.push(Line::styled(msg.as_str(), Style::new().fg(theme.success))),
+            Some(Err(msg)) => title.push(Line::styled(msg.as_str(), Style::new().fg(theme.error))),
+            None => {}
+        }
+        Paragraph::new(title).render(title_area, buf);
+
+        for (input, state, area, title) in [
+            (
+                Input::Filter,
+                &mut self.filter_state,
+                filter_area,
+                " / Filter ",
+            ),
+            (Input::Time, &mut self.time_state, time_area, " t Jump to "),
+        ] {
+            let focused = self.input == input;
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .border_style(if focused {
+                    theme.accent()
+                } else {
+                    Style::new()
+                })
+                .title(title)
+                .render(area, buf);
+            TextInput::default()
+                .hint_style(theme.muted())
+                .render_cursor(focused)
+                .render(area.inner(Margin::new(1, 1)), buf, state);
+        }
+
+        // 2 for the borders, 2 for the header and its margin, 3 for the column spacing
+        let height = usize::from(table_area.height.saturating_sub(4));
+        let message_width = usize::from(
+            table_area
+                .width
+                .saturating_sub(2 + 3 + TIME_WIDTH + SOURCE_WIDTH + LEVEL_WIDTH),
+        )
+        .max(1);
+        let row_height = |entry: &LogEntry| {
+            if self.wrap {
+                wrap(&entry.message, message_width).len()
+            } else {
+                1
+            }
+        };
+
+        if let Some(index) = self.view.jump_to.take() {
+            let mut used = 0;
+            let end = entries[index..]
+                .iter()
+                .take_while(|entry| {
+                    used += row_height(entry);
+                    used <= height
+                })
+                .count();
+            self.view.offset = entries.len().saturating_sub(index + end.max(1));
+        }
+
+        // find the oldest entry that still fits when ending at the scroll offset
+        let mut fits_from_top = 0;
+        let mut used = 0;
+        for entry in entries.iter() {
+            used += row_height(entry);
+            if used > height {
+                break;
+            }
+            fits_from_top += 1;
+        }
+        let max_offset = entries.len() - fits_from_top;
+        self.view.offset = self.view.offset.min(max_offset);
+        let end = entries.len() - self.view.offset;
+        let mut start = end;
+        let mut used = 0;
+        while start > 0 && used + row_height(entries[start - 1]) <= height {
+            used += row_height(entries[start - 1]);
+            start -= 1;
+        }
+
+        let header = ["Time", "Source", "Level", "Message"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(theme.muted())
+            .bottom_margin(1);
+        self.table_area = table_area;
+        // 1 for the border, 2 for the header and its margin
+        let mut y = table_area.y + 3;
+        let visible_rows = entries[start..end]
+            .iter()
+            .map(|entry| {
+                let rows = y..y + row_height(entry) as u16;
+                y = rows.end;
+                rows
+            })
+            .collect();
+        self.visible_rows = (start, visible_rows);
+
+        let rows = entries[start..end].iter().enumerate().map(|(i, entry)| {
+            let message = if self.wrap {
+                Text::from(
+                    wrap(&entry.message, message_width)
+                        .into_iter()
+                        .map(Line::raw)
+                        .collect::<Vec<_>>(),
+                )
+            } else {
+                Text::raw(truncate(&entry.message, message_width))
+            };
+            let height = message.height() as u16;
+            Row::new(vec![
+                Cell::from(entry.timestamp.format("%H:%M:%S").to_string()).style(theme.muted()),
+                Cell::from(entry.source.as_str()).style(source_style(
+                    &entry.source,
+                    &network.log_sources,
+                    theme,
+                )),
+                Cell::from(entry.level.to_string()).style(level_style(entry.level, theme)),
+                Cell::from(message),
+            ])
+            .height(height)
+            .style(if self.view.selected == Some(start + i) {
+                theme.selected()
+            } else {
+                Style::new()
+            })
+        });
+        let widths = [
+            Constraint::Length(TIME_WIDTH),
+            Constraint::Length(SOURCE_WIDTH),
+            Constraint::Length(LEVEL_WIDTH),
+            Constraint::Fill(1),
+        ];
+        Widget::render(
+            Table::new(rows, widths)
+                .header(header)
+                .block(Block::bordered().border_type(BorderType::Rounded)),
+            table_area,
+            buf,
+        );
+    }
+
+    /// Keeps the position in the logs of the `previous` network and restores
+    /// that of the `current` one.
+    fn on_network_change(&mut self, previous: usize, current: usize) {
+        let view = self.views.remove(&current).unwrap_or_default();
+        self.views
+            .insert(previous, mem::replace(&mut self.view, view));
+        self.status = None;
+    }
+
+    fn run_command(&mut self, command: &CommandKind, state: &AppState) -> bool {
+        match command {
+            CommandKind::Node(node) => {
+                self.set_filter(&format!("node:\"{}\"", node.to_lowercase()));
+            }
+            CommandKind::Since(range) => self.jump_back(*range, state.network()),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Copies the merged entries of `network` to pause the page on them.
+fn snapshot(network: &Network) -> Vec<LogEntry> {
+    merged(network).into_iter().cloned().collect()
+}
+
+fn merged(network: &Network) -> Vec<&LogEntry> {
+    let mut entries = network
+        .tx_logs
+        .iter()
+        .chain(&network.mpc_logs)
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.timestamp);
+    entries
+}
+
+fn filtered<'a>(
+    paused: Option<&'a [LogEntry]>,
+    filter: &LogFilter,
+    network: &'a Network,
+) -> Vec<&'a LogEntry> {
+    match paused {
+        Some(entries) => entries.iter().collect(),
+        None => merged(network),
+    }
+    .into_iter()
+    .filter(|entry| filter.matches(entry))
+    .collect()
+}
+
+/// Cuts `text` to `width` columns, ending it with `…` if it was cut.
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        used += c.width().unwrap_or(0);
+        if used + 1 > width {
+            break;
+        }
+        truncated.push(c);
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Wraps `text` at word boundaries, splitting words longer than `width`.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for mut word in text.split_whitespace() {
+        loop {
+            let len = line.width();
+            let word_len = word.width();
+            let needed = if len == 0 {
+                word_len
+            } else {
+                len + 1 + word_len
+            };
+            if needed <= width {
+                if len > 0 {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                break;
+            }
+            if len > 0 {
+                lines.push(std::mem::take(&mut line));
+                continue;
+            }
+            let split = fit(word, width);
+            lines.push(word[..split].to_string());
+            word = &word[split..];
+            if word.is_empty() {
+                break;
+            }
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Returns the length in bytes of the start of `text` that fits into `width`
+/// columns, at least one character so that wrapping always makes progress.
+fn fit(text: &str, width: usize) -> usize {
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return if i == 0 { c.len_utf8() } else { i };
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{logs::LogLevel, network::NetworkConfig};
+
+    use super::*;
+
+    /// A network whose MPC logs are one entry per second from `start` on.
+    fn network(start: DateTime<Local>, entries: i64) -> Network {
+        let mut network = Network::new(
+            0,
+            NetworkConfig {
+                name: "Base".to_string(),
+                outages: false,
+            },
+        );
+        network.tx_logs.clear();
+        network.mpc_logs = (0..entries)
+            .map(|i| LogEntry {
+                timestamp: start + TimeDelta::seconds(i),
+                ..LogEntry::new(LogLevel::Info, "MPC Node 1", format!("entry {i}"))
+            })
+            .collect();
+        network
+    }
+
+    #[test]
+    fn truncates_to_the_width_in_columns() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly", 7), "exactly");
+        assert_eq!(truncate("too long", 5), "too …");
+        assert_eq!(truncate("abc", 0), "…");
+        // wide characters take two columns
+        assert_eq!(truncate("証明の生成", 10), "証明の生成");
+        assert_eq!(truncate("証明の生成", 6), "証明…");
+        assert_eq!(truncate("証明の生成", 5), "証明…");
+        assert!(truncate("証明の生成", 5).width() <= 5);
+    }
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        assert_eq!(wrap("", 10), [""]);
+        assert_eq!(wrap("a b c", 10), ["a b c"]);
+        assert_eq!(wrap("proof took too long", 10), ["proof took", "too long"]);
+        // words longer than the width are split
+        assert_eq!(wrap("abcdefghij k", 4), ["abcd", "efgh", "ij k"]);
+    }
+
+    #[test]
+    fn wraps_wide_characters_by_columns() {
+        assert_eq!(wrap("証明の生成 完了", 6), ["証明の", "生成", "完了"]);
+        for line in wrap("証明の生成に失敗しました", 5) {
+            assert!(line.width() <= 5, "{line}");
+        }
+        // a character wider than the line still makes progress
+        assert_eq!(wrap("証明", 1), ["証", "明"]);
+        assert_eq!(wrap("ab", 0), ["a", "b"]);
+    }
+
+    #[test]
+    fn jumps_to_the_first_entry_at_the_time() {
+        let start = Local::now() - TimeDelta::minutes(10);
+        let network = network(start, 10);
+        let mut logs = Logs::new();
+
+        logs.jump_to_time(start + TimeDelta::milliseconds(2500), &network);
+        assert_eq!(logs.view.jump_to, Some(3));
+        assert!(logs.view.paused.is_some());
+        assert!(logs.status.is_none());
+
+        // a time after the newest entry shows the newest one
+        logs.jump_to_time(start + TimeDelta::minutes(1), &network);
+        assert_eq!(logs.view.jump_to, Some(9));
+    }
+
+    #[test]
+    fn jumping_before_the_oldest_entry_fails() {
+        let start = Local::now() - TimeDelta::minutes(10);
+        let network = network(start, 10);
+        let mut logs = Logs::new();
+
+        logs.jump_to_time(start - TimeDelta::seconds(1), &network);
+        assert_eq!(logs.view.jump_to, None);
+        assert!(logs.view.paused.is_none());
+        let Some(Err(msg)) = &logs.status else {
+            panic!("no error for a jump before the oldest entry");
+        };
+        assert!(msg.starts_with("No entries that old"));
+    }
+
+    #[test]
+    fn jumping_in_an_empty_log_fails() {
+        let network = network(Local::now(), 0);
+        let mut logs = Logs::new();
+
+        logs.jump_to_time(Local::now(), &network);
+        assert_eq!(logs.view.jump_to, None);
+        assert!(logs.view.paused.is_none());
+        assert_eq!(logs.status, Some(Err("No entries to jump to".to_string())));
+    }
+}
@@ -1,3 +1,129 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyEvent, MouseEvent},
+    layout::{Position, Rect},
+};
+use tui_scrollview::ScrollViewState;
+
+use crate::{
+    AppState,
+    keymap::{Action, Context},
+    widgets::command_palette::CommandKind,
+};
+
+pub mod alerts;
+pub mod compare;
 pub mod dashboard;
+pub mod diagnostics;
 pub mod intro;
+pub mod logs;
 pub mod wallets;
+
+/// A screen of the app, listed in the menu in the order of [`pages`].
+pub trait Page {
+    fn title(&self) -> &'static str;
+
+    /// Nerd font icon shown in front of the title in the menu.
+    fn icon(&self) -> &'static str;
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &AppState);
+
+    fn on_tick(&mut self) {}
+
+    /// Called when the selected network changes from index `previous` to
+    /// `current`, for pages that keep view state per network.
+    fn on_network_change(&mut self, _previous: usize, _current: usize) {}
+
+    /// Returns the state of the scroll view the page is rendered in, if any.
+    fn scroll_view_state(&mut self) -> Option<&mut ScrollViewState> {
+        None
+    }
+
+    fn scroll_down(&mut self) {
+        if let Some(state) = self.scroll_view_state() {
+            state.scroll_down();
+        }
+    }
+
+    fn scroll_up(&mut self) {
+        if let Some(state) = self.scroll_view_state() {
+            state.scroll_up();
+        }
+    }
+
+    /// Returns the key binding context of the focused part of the page.
+    fn context(&self) -> Context {
+        Context::Global
+    }
+
+    /// Returns the actions the page handles in its current context.
+    fn actions(&self) -> &'static [Action] {
+        &[]
+    }
+
+    /// Handles `action` on the page, returns whether it was consumed.
+    fn handle_action(&mut self, _action: Action, _state: &AppState) -> bool {
+        false
+    }
+
+    /// Returns the actions the page handles in any context, offered in the
+    /// command palette.
+    fn palette_actions(&self) -> &'static [Action] {
+        &[]
+    }
+
+    /// Runs `action` picked in the command palette, moving the focus to where
+    /// it applies first, returns whether it was consumed.
+    fn run_palette_action(&mut self, action: Action, state: &AppState) -> bool {
+        self.handle_action(action, state)
+    }
+
+    /// Types `key` into the focused input of the page.
+    fn handle_input(&mut self, _key: KeyEvent) {}
+
+    /// Handles `mouse` over the page, returns whether it was consumed.
+    fn handle_mouse(&mut self, _mouse: MouseEvent, _state: &AppState) -> bool {
+        false
+    }
+
+    /// Runs a command palette command, returns whether it targets this page.
+    fn run_command(&mut self, _command: &CommandKind, _state: &AppState) -> bool {
+        false
+    }
+
+    /// Takes the outcome of the last action to show in the footer, like the
+    /// path of an export.
+    fn take_notice(&mut self) -> Option<Result<String, String>> {
+        None
+    }
+}
+
+/// The registry of all pages in menu order, new pages only need to be added here.
+pub fn pages() -> Vec<Box<dyn Page>> {
+    vec![
+        Box::new(intro::Intro::new()),
+        Box::new(dashboard::Dashboard::new()),
+        Box::new(wallets::Wallets::new()),
+        Box::new(alerts::Alerts::new()),
+        Box::new(compare::Compare::new()),
+        Box::new(logs::Logs::new()),
+        Box::new(diagnostics::Diagnostics::new()),
+    ]
+}
+
+/// Translates `mouse` on a page whose scroll view is rendered in `area` into
+/// the coordinates of the scroll view content.
+pub fn content_position(
+    area: Rect,
+    state: &ScrollViewState,
+    mouse: &MouseEvent,
+) -> Option<Position> {
+    let position = Position::new(mouse.column, mouse.row);
+    area.contains(position).then(|| {
+        let offset = state.offset();
+        Position::new(
+            position.x - area.x + offset.x,
+            position.y - area.y + offset.y,
+        )
+    })
+}
@@ -1,64 +1,163 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect, Size},
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    layout::{Constraint, Layout, Position, Rect, Size},
     style::Style,
-    text::Line,
+    text::{Line, Span},
     widgets::{Paragraph, StatefulWidget, Widget},
 };
 use tui_scrollview::{ScrollView, ScrollViewState};
 
 use crate::{
-    SinSignal,
+    AppState,
+    export::{self, ExportFormat},
+    keymap::{Action, Context},
+    pages::{Page, content_position},
+    source::SourceKind,
     widgets::{
+        command_palette::CommandKind,
+        freshness::Freshness,
         text_input::TextInputState,
+        tooltip::Tooltip,
         txs_per_second::TxsPerSecond,
-        wallets_table::{WalletsTable, WalletsTableState},
+        wallets_table::{Wallet, WalletsTable, WalletsTableState},
     },
 };
 
 pub struct Wallets {
     pub scroll_view_state: ScrollViewState,
-    window: [f64; 2],
-    txs_per_second_signal: SinSignal,
-    txs_per_second_data: Vec<(f64, f64)>,
     pub wallets_table_state: WalletsTableState,
+    /// Screen area of the scroll view, used to translate mouse events
+    area: Rect,
+    /// Mouse position in the scroll view content
+    hover: Option<Position>,
+    /// Outcome of the last export
+    notice: Option<Result<String, String>>,
 }
 
 impl Wallets {
     pub fn new() -> Self {
-        let mut txs_per_second_signal = SinSignal::new(0.1, 2.0, 10.0);
-        let txs_per_second_data = txs_per_second_signal
-            .by_ref()
-            .take(200)
-            .collect::<Vec<(f64, f64)>>();
         Self {
             scroll_view_state: ScrollViewState::default(),
-            window: [0.0, 20.0],
-            txs_per_second_signal,
-            txs_per_second_data,
-            wallets_table_state: WalletsTableState {
-                search_state: TextInputState::default().hint_text(" Search..."),
-                search_focused: false,
-            },
+            wallets_table_state: WalletsTableState::new(
+                TextInputState::default().hint_text(" Search..."),
+            ),
+            area: Rect::default(),
+            hover: None,
+            notice: None,
         }
     }
 
-    pub fn on_tick(&mut self) {
-        self.window[0] += 1.0;
-        self.window[1] += 1.0;
+    /// Selects the wallet at `index` of the network, clearing the search so
+    /// that it is listed.
+    fn select_wallet(&mut self, index: usize) {
+        let search_state = &mut self.wallets_table_state.search_state;
+        search_state.text.clear();
+        search_state.cursor_pos = 0;
+        self.wallets_table_state.table_state.select(Some(index));
+    }
+
+    /// Exports the wallets of the selected network matching the search.
+    fn export(&mut self, state: &AppState, format: ExportFormat) {
+        let network = state.network();
+        let wallets = filtered(
+            &network.wallets,
+            &self.wallets_table_state.search_state.text,
+        );
+        self.notice = Some(
+            export::wallets(&network.name, &wallets, format, &state.output_dir)
+                .map(|(path, count)| format!("Exported {count} wallets to {}", path.display()))
+                .map_err(|err| format!("Export failed: {err:#}")),
+        );
+    }
+}
+
+impl Page for Wallets {
+    fn title(&self) -> &'static str {
+        "Wallets"
+    }
+
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    fn scroll_view_state(&mut self) -> Option<&mut ScrollViewState> {
+        Some(&mut self.scroll_view_state)
+    }
+
+    /// Returns the key binding context of the page.
+    fn context(&self) -> Context {
+        if self.wallets_table_state.search_focused {
+            Context::Search
+        } else {
+            Context::Global
+        }
+    }
+
+    /// Returns the actions the page handles in its current context.
+    fn actions(&self) -> &'static [Action] {
+        match self.context() {
+            Context::Search => &[Action::Unfocus],
+            Context::Global | Context::Table => &[Action::FocusSearch, Action::Export],
+        }
+    }
+
+    fn palette_actions(&self) -> &'static [Action] {
+        &[Action::FocusSearch, Action::Export]
+    }
 
-        self.txs_per_second_data.drain(0..10);
-        self.txs_per_second_data
-            .extend(self.txs_per_second_signal.by_ref().take(10));
+    /// Leaves the search box and handles `action`.
+    fn run_palette_action(&mut self, action: Action, state: &AppState) -> bool {
+        self.wallets_table_state.search_focused = false;
+        self.handle_action(action, state)
     }
 
-    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+    /// Handles `action` on the page, returns whether it was consumed.
+    fn handle_action(&mut self, action: Action, state: &AppState) -> bool {
+        let search_focused = &mut self.wallets_table_state.search_focused;
+        match (*search_focused, action) {
+            (false, Action::FocusSearch) => *search_focused = true,
+            (false, Action::Export) => self.export(state, state.export_format),
+            // the table is filtered while typing
+            (true, Action::Unfocus | Action::Submit) => *search_focused = false,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Types `key` into the search box.
+    fn handle_input(&mut self, key: KeyEvent) {
+        self.wallets_table_state
+            .search_state
+            .handle_events(key.code, key.modifiers);
+        self.wallets_table_state.table_state.select(None);
+    }
+
+    /// Handles `mouse` over the page, returns whether it was consumed.
+    fn handle_mouse(&mut self, mouse: MouseEvent, _state: &AppState) -> bool {
+        let Some(position) = content_position(self.area, &self.scroll_view_state, &mouse) else {
+            self.hover = None;
+            return false;
+        };
+        match mouse.kind {
+            MouseEventKind::Moved => {
+                self.hover = Some(position);
+                true
+            }
+            MouseEventKind::Down(MouseButton::Left) => self.wallets_table_state.click(position),
+            _ => false,
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &AppState) {
+        let network = state.network();
         let height = 75;
         let mut scroll_view = ScrollView::new(Size::new(area.width, height))
             .vertical_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Automatic)
             .horizontal_scrollbar_visibility(tui_scrollview::ScrollbarVisibility::Never);
         let scroll_view_area = area;
         let scroll_view_buf = buf;
+        self.area = scroll_view_area;
         let mut area = *scroll_view.buf().area();
         if scroll_view_area.height < height {
             area.width -= 2; // adjust for scrollbar if vertical scrollbar is visible
@@ -72,15 +171,39 @@ impl Wallets {
         ])
         .areas(area);
 
-        TxsPerSecond::new(self.window, &self.txs_per_second_data).render(txs_area, buf);
+        let theme = state.theme();
+        let txs_per_second = TxsPerSecond::new(network.window, &network.txs_per_second_data, theme);
+        let tooltip = self.hover.and_then(|position| {
+            txs_per_second
+                .tooltip(txs_area, position)
+                .map(|lines| Tooltip::new(lines, position))
+        });
+        Freshness::new(txs_per_second, network.source(SourceKind::Metrics), theme)
+            .render(txs_area, buf);
 
+        let wallets = network.source(SourceKind::Wallets);
         Paragraph::new(vec![
             Line::raw(""),
-            Line::styled("Wallets", Style::new().bold()),
+            Line::from(vec![
+                Span::styled("Wallets", Style::new().bold()),
+                Span::styled(format!(" {}", wallets.updated_ago()), theme.muted()),
+            ]),
         ])
         .render(title_area, buf);
 
-        WalletsTable::new().render(txs_table, buf, &mut self.wallets_table_state);
+        let matching = filtered(
+            &network.wallets,
+            &self.wallets_table_state.search_state.text,
+        );
+        Freshness::new(WalletsTable::new(&matching, theme), wallets, theme).render(
+            txs_table,
+            buf,
+            &mut self.wallets_table_state,
+        );
+
+        if let Some(tooltip) = tooltip {
+            tooltip.render(area, buf);
+        }
 
         scroll_view.render(
             scroll_view_area,
@@ -88,4 +211,30 @@ impl Wallets {
             &mut self.scroll_view_state,
         );
     }
+
+    fn run_command(&mut self, command: &CommandKind, state: &AppState) -> bool {
+        match command {
+            CommandKind::Wallet(index) => self.select_wallet(*index),
+            CommandKind::Export(format) => self.export(state, *format),
+            _ => return false,
+        }
+        true
+    }
+
+    fn take_notice(&mut self) -> Option<Result<String, String>> {
+        self.notice.take()
+    }
+}
+
+/// Returns the wallets whose address or last transfer contains `query`,
+/// ignoring case.
+fn filtered<'a>(wallets: &'a [Wallet], query: &str) -> Vec<&'a Wallet> {
+    let query = query.to_lowercase();
+    wallets
+        .iter()
+        .filter(|wallet| {
+            wallet.address.to_lowercase().contains(&query)
+                || wallet.last_transfer.to_lowercase().contains(&query)
+        })
+        .collect()
 }
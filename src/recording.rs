@@ -0,0 +1,92 @@
+//! Recording of the rendered frames as an asciicast v2 file, playable with
+//! asciinema.
+
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::Instant,
+};
+
+use chrono::Utc;
+use eyre::Context as _;
+use ratatui::buffer::Buffer;
+use serde_json::json;
+
+use crate::screenshot;
+
+/// Writes every frame that differs from the previous one as an output event,
+/// redrawing only the rows that changed.
+pub struct Recorder {
+    writer: BufWriter<File>,
+    started: Instant,
+    previous: Option<Buffer>,
+}
+
+impl Recorder {
+    /// Creates the recording at `path`, the header is written with the first
+    /// frame since it holds the size of the terminal.
+    pub fn create(path: &Path) -> eyre::Result<Self> {
+        let file =
+            File::create(path).wrap_err_with(|| format!("cannot create {}", path.display()))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+            previous: None,
+        })
+    }
+
+    /// Records the frame rendered to `buf`.
+    pub fn record(&mut self, buf: &Buffer) -> eyre::Result<()> {
+        let lines = screenshot::ansi_lines(buf);
+        let mut output = String::new();
+        match &self.previous {
+            None => {
+                let header = json!({
+                    "version": 2,
+                    "width": buf.area.width,
+                    "height": buf.area.height,
+                    "timestamp": Utc::now().timestamp(),
+                    "title": "Merces",
+                    "env": { "TERM": std::env::var("TERM").unwrap_or_default() },
+                });
+                writeln!(self.writer, "{header}")?;
+                // hide the cursor and start from a blank screen
+                output.push_str("\x1b[?25l\x1b[2J");
+                redraw(&mut output, &lines, |_| true);
+            }
+            Some(previous) if previous.area != buf.area => {
+                self.event("r", format!("{}x{}", buf.area.width, buf.area.height))?;
+                output.push_str("\x1b[2J");
+                redraw(&mut output, &lines, |_| true);
+            }
+            Some(previous) if previous == buf => return Ok(()),
+            Some(previous) => {
+                let changed = |y: u16| (0..buf.area.width).any(|x| previous[(x, y)] != buf[(x, y)]);
+                redraw(&mut output, &lines, changed);
+            }
+        }
+        self.event("o", output)?;
+        self.writer.flush()?;
+        self.previous = Some(buf.clone());
+        Ok(())
+    }
+
+    /// Writes an event of `kind` with `data` at the current time.
+    fn event(&mut self, kind: &str, data: String) -> eyre::Result<()> {
+        let time = self.started.elapsed().as_secs_f64();
+        writeln!(self.writer, "{}", json!([time, kind, data]))?;
+        Ok(())
+    }
+}
+
+/// Appends the `lines` whose rows are `changed` to `output`, each positioned
+/// at the start of its row.
+fn redraw(output: &mut String, lines: &[String], changed: impl Fn(u16) -> bool) {
+    for (y, line) in lines.iter().enumerate() {
+        if changed(y as u16) {
+            let _ = write!(output, "\x1b[{};1H{line}", y + 1);
+        }
+    }
+}
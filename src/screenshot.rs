@@ -0,0 +1,488 @@
+//! Screenshots of the rendered page as SVG, HTML or ANSI-colored text.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::Local;
+use eyre::Context as _;
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use serde::Deserialize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::export;
+
+/// Colors of the terminal's default foreground and background, assumed dark.
+const DEFAULT_FG: (u8, u8, u8) = (0xe5, 0xe5, 0xe5);
+const DEFAULT_BG: (u8, u8, u8) = (0x1e, 0x1e, 0x1e);
+
+/// Size of a cell in the SVG, in pixels.
+const CELL_WIDTH: f64 = 8.4;
+const CELL_HEIGHT: f64 = 17.0;
+const FONT_SIZE: f64 = 14.0;
+
+/// The xterm colors of the 16 named colors.
+const NAMED_COLORS: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenshotFormat {
+    #[default]
+    Svg,
+    Html,
+    /// Text with ANSI escape codes, shown in color by `cat` or `less -R`
+    Ansi,
+}
+
+impl ScreenshotFormat {
+    pub const ALL: [Self; 3] = [Self::Svg, Self::Html, Self::Ansi];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Svg => "SVG",
+            Self::Html => "HTML",
+            Self::Ansi => "ANSI text",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Html => "html",
+            Self::Ansi => "ans",
+        }
+    }
+}
+
+/// Writes `buf` in `format` to a timestamped file named after `page` in the
+/// output `dir` and returns its path.
+pub fn save(
+    buf: &Buffer,
+    page: &str,
+    format: ScreenshotFormat,
+    dir: &Path,
+) -> eyre::Result<PathBuf> {
+    let path = export::output_path(
+        dir,
+        &format!(
+            "merces-{}-{}.{}",
+            page.to_lowercase(),
+            Local::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ),
+    )?;
+    let content = match format {
+        ScreenshotFormat::Svg => to_svg(buf),
+        ScreenshotFormat::Html => to_html(buf),
+        ScreenshotFormat::Ansi => to_ansi(buf),
+    };
+    fs::write(&path, content).wrap_err_with(|| format!("cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Text of consecutive cells with the same style.
+struct Run {
+    /// Column of the first cell
+    x: u16,
+    /// Number of cells covered
+    width: u16,
+    fg: Color,
+    bg: Color,
+    modifier: Modifier,
+    text: String,
+}
+
+impl Run {
+    /// Returns the colors to draw with, swapped if the run is reversed.
+    fn colors(&self) -> ((u8, u8, u8), (u8, u8, u8)) {
+        let fg = rgb(self.fg).unwrap_or(DEFAULT_FG);
+        let bg = rgb(self.bg).unwrap_or(DEFAULT_BG);
+        if self.modifier.contains(Modifier::REVERSED) {
+            (bg, fg)
+        } else {
+            (fg, bg)
+        }
+    }
+}
+
+/// Splits every row of `buf` into runs, the cells hidden by wide symbols are
+/// left out and images are replaced by blanks.
+fn runs(buf: &Buffer) -> Vec<Vec<Run>> {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut runs: Vec<Run> = Vec::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = &buf[(x, y)];
+                // image protocols write their escape sequences into a cell
+                let symbol = if cell.symbol().is_empty() || cell.symbol().starts_with('\x1b') {
+                    " "
+                } else {
+                    cell.symbol()
+                };
+                let width = symbol.width().max(1) as u16;
+                match runs.last_mut() {
+                    Some(run)
+                        if run.fg == cell.fg
+                            && run.bg == cell.bg
+                            && run.modifier == cell.modifier =>
+                    {
+                        run.width += width;
+                        run.text.push_str(symbol);
+                    }
+                    _ => runs.push(Run {
+                        x: x - area.left(),
+                        width,
+                        fg: cell.fg,
+                        bg: cell.bg,
+                        modifier: cell.modifier,
+                        text: symbol.to_string(),
+                    }),
+                }
+                x += width;
+            }
+            runs
+        })
+        .collect()
+}
+
+fn to_ansi(buf: &Buffer) -> String {
+    ansi_lines(buf)
+        .into_iter()
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Returns every row of `buf` as text with ANSI escape codes, ending with a
+/// reset of the style.
+pub fn ansi_lines(buf: &Buffer) -> Vec<String> {
+    runs(buf)
+        .into_iter()
+        .map(|row| {
+            let mut line = String::new();
+            for run in row {
+                let mut codes = vec!["0".to_string()];
+                codes.extend(modifier_codes(run.modifier).iter().map(u8::to_string));
+                codes.extend(color_code(run.fg, false));
+                codes.extend(color_code(run.bg, true));
+                let _ = write!(line, "\x1b[{}m{}", codes.join(";"), run.text);
+            }
+            line.push_str("\x1b[0m");
+            line
+        })
+        .collect()
+}
+
+fn to_html(buf: &Buffer) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Merces</title>\n</head>\n\
+         <body style=\"margin: 0; background: {bg}\">\n\
+         <pre style=\"margin: 0; padding: 1em; color: {fg}; background: {bg}; \
+         font-family: monospace; line-height: 1.2\">",
+        fg = hex(DEFAULT_FG),
+        bg = hex(DEFAULT_BG),
+    );
+    for row in runs(buf) {
+        for run in row {
+            let (fg, bg) = run.colors();
+            let mut style = format!("color: {}", hex(fg));
+            if bg != DEFAULT_BG {
+                let _ = write!(style, "; background: {}", hex(bg));
+            }
+            style.push_str(&font_style(run.modifier));
+            let _ = write!(out, "<span style=\"{style}\">{}</span>", escape(&run.text));
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn to_svg(buf: &Buffer) -> String {
+    let width = f64::from(buf.area.width) * CELL_WIDTH;
+    let height = f64::from(buf.area.height) * CELL_HEIGHT;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"{FONT_SIZE}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(DEFAULT_BG)
+    );
+    for (y, row) in runs(buf).iter().enumerate() {
+        let top = y as f64 * CELL_HEIGHT;
+        for run in row {
+            let (_, bg) = run.colors();
+            if bg != DEFAULT_BG {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{:.1}\" y=\"{top}\" width=\"{:.1}\" height=\"{CELL_HEIGHT}\" fill=\"{}\"/>",
+                    f64::from(run.x) * CELL_WIDTH,
+                    f64::from(run.width) * CELL_WIDTH,
+                    hex(bg)
+                );
+            }
+        }
+        for run in row {
+            if run.text.trim().is_empty() {
+                continue;
+            }
+            let (fg, _) = run.colors();
+            let mut attributes = String::new();
+            if run.modifier.contains(Modifier::BOLD) {
+                attributes.push_str(" font-weight=\"bold\"");
+            }
+            if run.modifier.contains(Modifier::ITALIC) {
+                attributes.push_str(" font-style=\"italic\"");
+            }
+            if run.modifier.contains(Modifier::UNDERLINED) {
+                attributes.push_str(" text-decoration=\"underline\"");
+            }
+            if run.modifier.contains(Modifier::DIM) {
+                attributes.push_str(" opacity=\"0.6\"");
+            }
+            // the text is stretched to the cells so that box drawing lines up
+            let _ = writeln!(
+                out,
+                "<text x=\"{:.1}\" y=\"{:.1}\" textLength=\"{:.1}\" lengthAdjust=\"spacingAndGlyphs\" \
+                 fill=\"{}\" xml:space=\"preserve\"{attributes}>{}</text>",
+                f64::from(run.x) * CELL_WIDTH,
+                top + CELL_HEIGHT * 0.8,
+                f64::from(run.width) * CELL_WIDTH,
+                hex(fg),
+                escape(&run.text)
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Returns the SGR codes of `modifier`.
+fn modifier_codes(modifier: Modifier) -> Vec<u8> {
+    [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::REVERSED, 7),
+        (Modifier::CROSSED_OUT, 9),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| modifier.contains(*flag))
+    .map(|(_, code)| code)
+    .collect()
+}
+
+/// Returns the SGR code of `color` as foreground or background, `None` for
+/// the default color.
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Indexed(index) => format!("{};5;{index}", base + 8),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+        color => {
+            let index = named_index(color)?;
+            if index < 8 {
+                (base + index).to_string()
+            } else {
+                (base + 60 + index - 8).to_string()
+            }
+        }
+    };
+    Some(code)
+}
+
+/// Returns the index of a named color in the 16 color palette.
+fn named_index(color: Color) -> Option<u8> {
+    let index = match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        _ => return None,
+    };
+    Some(index)
+}
+
+/// Returns the RGB value of `color` with the xterm palette, `None` for the
+/// default color.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index @ 0..16) => Some(NAMED_COLORS[usize::from(index)]),
+        Color::Indexed(index @ 16..232) => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            Some((level(index / 36), level(index / 6 % 6), level(index % 6)))
+        }
+        Color::Indexed(index) => {
+            let gray = 8 + (index - 232) * 10;
+            Some((gray, gray, gray))
+        }
+        color => named_index(color).map(|index| NAMED_COLORS[usize::from(index)]),
+    }
+}
+
+fn font_style(modifier: Modifier) -> String {
+    let mut style = String::new();
+    if modifier.contains(Modifier::BOLD) {
+        style.push_str("; font-weight: bold");
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        style.push_str("; font-style: italic");
+    }
+    if modifier.contains(Modifier::UNDERLINED) {
+        style.push_str("; text-decoration: underline");
+    }
+    if modifier.contains(Modifier::DIM) {
+        style.push_str("; opacity: 0.6");
+    }
+    style
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Escapes `text` for HTML and SVG.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{layout::Rect, style::Style};
+
+    use super::*;
+
+    /// A one row buffer with `text` in `style` followed by a default cell.
+    fn buffer(text: &str, style: Style) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, text.width() as u16 + 1, 1));
+        buf.set_string(0, 0, text, style);
+        buf
+    }
+
+    #[test]
+    fn maps_colors_to_rgb() {
+        assert_eq!(rgb(Color::Reset), None);
+        assert_eq!(rgb(Color::Red), Some((0xcd, 0x00, 0x00)));
+        assert_eq!(rgb(Color::LightBlue), Some((0x5c, 0x5c, 0xff)));
+        assert_eq!(rgb(Color::Rgb(1, 2, 3)), Some((1, 2, 3)));
+        assert_eq!(rgb(Color::Indexed(9)), rgb(Color::LightRed));
+        // the 6x6x6 color cube and the gray ramp
+        assert_eq!(rgb(Color::Indexed(16)), Some((0, 0, 0)));
+        assert_eq!(rgb(Color::Indexed(122)), Some((0x87, 0xff, 0xd7)));
+        assert_eq!(rgb(Color::Indexed(231)), Some((0xff, 0xff, 0xff)));
+        assert_eq!(rgb(Color::Indexed(232)), Some((8, 8, 8)));
+        assert_eq!(rgb(Color::Indexed(255)), Some((0xee, 0xee, 0xee)));
+    }
+
+    #[test]
+    fn maps_colors_to_sgr_codes() {
+        assert_eq!(color_code(Color::Reset, false), None);
+        assert_eq!(color_code(Color::Red, false).as_deref(), Some("31"));
+        assert_eq!(color_code(Color::Red, true).as_deref(), Some("41"));
+        assert_eq!(color_code(Color::LightCyan, false).as_deref(), Some("96"));
+        assert_eq!(color_code(Color::DarkGray, true).as_deref(), Some("100"));
+        assert_eq!(
+            color_code(Color::Indexed(122), false).as_deref(),
+            Some("38;5;122")
+        );
+        assert_eq!(
+            color_code(Color::Rgb(1, 2, 3), true).as_deref(),
+            Some("48;2;1;2;3")
+        );
+        assert_eq!(
+            modifier_codes(Modifier::BOLD | Modifier::UNDERLINED),
+            [1, 4]
+        );
+    }
+
+    #[test]
+    fn writes_ansi_runs() {
+        let buf = buffer("ok", Style::new().fg(Color::Green).bold());
+        assert_eq!(to_ansi(&buf), "\x1b[0;1;32mok\x1b[0m \x1b[0m\n");
+    }
+
+    #[test]
+    fn writes_html_spans() {
+        let buf = buffer(
+            "<a&b>",
+            Style::new().fg(Color::Red).bg(Color::Blue).italic(),
+        );
+        let html = to_html(&buf);
+        assert!(html.contains(
+            "<span style=\"color: #cd0000; background: #0000ee; font-style: italic\">\
+             &lt;a&amp;b&gt;</span>"
+        ));
+        // the default background is left to the page
+        assert!(html.contains("<span style=\"color: #e5e5e5\"> </span>"));
+    }
+
+    #[test]
+    fn writes_svg_text_and_backgrounds() {
+        let buf = buffer("hi", Style::new().fg(Color::Rgb(1, 2, 3)).reversed());
+        let svg = to_svg(&buf);
+        // reversed runs swap the colors
+        assert!(svg.contains(&format!(
+            "<rect x=\"0.0\" y=\"0\" width=\"{:.1}\" height=\"{CELL_HEIGHT}\" fill=\"#010203\"/>",
+            2.0 * CELL_WIDTH
+        )));
+        assert!(svg.contains("fill=\"#1e1e1e\" xml:space=\"preserve\">hi</text>"));
+    }
+
+    #[test]
+    fn saves_into_the_output_dir() {
+        let dir = std::env::temp_dir().join(format!("merces-screenshot-{}", std::process::id()));
+        let buf = buffer("ok", Style::new());
+        let path = save(&buf, "Dashboard", ScreenshotFormat::Ansi, &dir).unwrap();
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        assert!(
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("merces-dashboard-")
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), to_ansi(&buf));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, TimeDelta};
+use tracing::{info, warn};
+
+/// Delay before the first retry of a failed source, doubled on every further
+/// failure.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Consecutive failures after which the circuit breaker opens and the source
+/// is considered failed.
+const BREAKER_THRESHOLD: u32 = 5;
+/// Time the circuit breaker stays open before a single attempt is let through.
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Data delivered separately for every network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// Transactions per second, node utilization and the totals
+    Metrics,
+    /// Published blocks shown in the transaction logs
+    Blocks,
+    MpcLogs,
+    Wallets,
+}
+
+impl SourceKind {
+    pub const ALL: [Self; 4] = [Self::Metrics, Self::Blocks, Self::MpcLogs, Self::Wallets];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Metrics => "metrics",
+            Self::Blocks => "blocks",
+            Self::MpcLogs => "mpc logs",
+            Self::Wallets => "wallets",
+        }
+    }
+
+    /// How long the source may go without an update before its data is
+    /// considered stale.
+    pub fn stale_after(&self) -> TimeDelta {
+        match self {
+            Self::Metrics | Self::Blocks | Self::MpcLogs => TimeDelta::seconds(10),
+            Self::Wallets => TimeDelta::minutes(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Connection {
+    Connected,
+    Reconnecting,
+    Failed,
+}
+
+impl Connection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Connected => "connected",
+            Self::Reconnecting => "reconnecting",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Connection and freshness of the data of a source.
+///
+/// Every fetch from the source goes through [`SourceStatus::poll`], which
+/// retries failed sources with exponential backoff and stops trying for a
+/// while after repeated failures, like a circuit breaker.
+#[derive(Debug, Clone)]
+pub struct SourceStatus {
+    pub kind: SourceKind,
+    pub connection: Connection,
+    /// Number of updates received
+    pub updates: u64,
+    pub updated_at: Option<DateTime<Local>>,
+    /// Number of consecutive failed fetches
+    pub failures: u32,
+    /// Error of the last failed fetch, cleared once the source delivers again
+    pub error: Option<String>,
+    /// No fetch is attempted before this instant, which unlike the wall clock
+    /// does not jump
+    pub retry_at: Option<Instant>,
+}
+
+impl SourceStatus {
+    pub fn new(kind: SourceKind) -> Self {
+        Self {
+            kind,
+            connection: Connection::Connected,
+            updates: 0,
+            updated_at: None,
+            failures: 0,
+            error: None,
+            retry_at: None,
+        }
+    }
+
+    /// Fetches from the source of `network` unless it is backing off, and
+    /// returns the data on success.
+    ///
+    /// Failures never propagate, they are logged and kept in the status while
+    /// the last data received stays in place.
+    pub fn poll<T>(&mut self, network: &str, fetch: impl FnOnce() -> eyre::Result<T>) -> Option<T> {
+        self.poll_at(Instant::now(), network, fetch)
+    }
+
+    fn poll_at<T>(
+        &mut self,
+        now: Instant,
+        network: &str,
+        fetch: impl FnOnce() -> eyre::Result<T>,
+    ) -> Option<T> {
+        if self.retry_at.is_some_and(|at| now < at) {
+            return None;
+        }
+        match fetch() {
+            Ok(data) => {
+                if self.failures > 0 {
+                    info!(
+                        network,
+                        source = self.kind.name(),
+                        failures = self.failures,
+                        "reconnected"
+                    );
+                }
+                self.connection = Connection::Connected;
+                self.failures = 0;
+                self.error = None;
+                self.retry_at = None;
+                self.updated();
+                Some(data)
+            }
+            Err(err) => {
+                self.failures += 1;
+                let delay = if self.failures >= BREAKER_THRESHOLD {
+                    self.connection = Connection::Failed;
+                    BREAKER_COOLDOWN
+                } else {
+                    self.connection = Connection::Reconnecting;
+                    backoff(self.failures)
+                };
+                warn!(
+                    network,
+                    source = self.kind.name(),
+                    failures = self.failures,
+                    retry_in = ?delay,
+                    "fetch failed: {err:#}"
+                );
+                self.error = Some(format!("{err:#}"));
+                self.retry_at = Some(now + delay);
+                None
+            }
+        }
+    }
+
+    /// Returns the time until the next fetch of a source that is backing off.
+    pub fn retry_in(&self) -> Option<TimeDelta> {
+        self.retry_at.map(|at| {
+            TimeDelta::from_std(at.saturating_duration_since(Instant::now()))
+                .unwrap_or(TimeDelta::MAX)
+        })
+    }
+
+    /// Records an update received now.
+    pub fn updated(&mut self) {
+        self.updates += 1;
+        self.updated_at = Some(Local::now());
+    }
+
+    /// Returns the time since the last update.
+    pub fn age(&self) -> Option<TimeDelta> {
+        self.updated_at.map(|at| Local::now() - at)
+    }
+
+    /// Returns whether the source stopped delivering, data that was never
+    /// updated is stale.
+    pub fn is_stale(&self) -> bool {
+        self.age().is_none_or(|age| age > self.kind.stale_after())
+    }
+
+    /// Describes when the data was last updated, like "updated 3 s ago".
+    pub fn updated_ago(&self) -> String {
+        match self.age() {
+            Some(age) => format!("updated {} ago", format_age(age)),
+            None => "never updated".to_string(),
+        }
+    }
+}
+
+/// Returns the delay before the retry following the `failures`th consecutive
+/// failure.
+fn backoff(failures: u32) -> Duration {
+    let factor = 1 << (failures - 1).min(16);
+    (INITIAL_BACKOFF * factor).min(MAX_BACKOFF)
+}
+
+/// Formats `age` in the largest unit that keeps it at least one.
+pub fn format_age(age: TimeDelta) -> String {
+    match age.num_seconds() {
+        seconds @ ..60 => format!("{} s", seconds.max(0)),
+        seconds @ ..3600 => format!("{} min", seconds / 60),
+        seconds => format!("{} h", seconds / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::eyre;
+
+    use super::*;
+
+    fn fail(source: &mut SourceStatus, now: Instant) -> Option<()> {
+        source.poll_at(now, "test", || Err(eyre!("unreachable")))
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        assert_eq!(backoff(1), Duration::from_millis(500));
+        assert_eq!(backoff(2), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(2));
+        assert_eq!(backoff(7), Duration::from_secs(30));
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn failed_source_is_not_fetched_while_backing_off() {
+        let mut source = SourceStatus::new(SourceKind::Metrics);
+        let start = Instant::now();
+        assert_eq!(fail(&mut source, start), None);
+        assert_eq!(source.connection, Connection::Reconnecting);
+        assert_eq!(source.failures, 1);
+        assert_eq!(source.retry_at, Some(start + INITIAL_BACKOFF));
+        assert_eq!(source.error.as_deref(), Some("unreachable"));
+
+        let before_retry = start + INITIAL_BACKOFF - Duration::from_millis(1);
+        let fetched = source.poll_at(before_retry, "test", || -> eyre::Result<()> {
+            panic!("fetched while backing off")
+        });
+        assert_eq!(fetched, None);
+        assert_eq!(source.failures, 1);
+
+        let retry = start + INITIAL_BACKOFF;
+        assert_eq!(fail(&mut source, retry), None);
+        assert_eq!(source.failures, 2);
+        assert_eq!(source.retry_at, Some(retry + backoff(2)));
+    }
+
+    #[test]
+    fn breaker_opens_after_repeated_failures_and_closes_on_success() {
+        let mut source = SourceStatus::new(SourceKind::Blocks);
+        let mut now = Instant::now();
+        for failures in 1..BREAKER_THRESHOLD {
+            fail(&mut source, now);
+            assert_eq!(source.connection, Connection::Reconnecting);
+            now += backoff(failures);
+        }
+        fail(&mut source, now);
+        assert_eq!(source.connection, Connection::Failed);
+        assert_eq!(source.failures, BREAKER_THRESHOLD);
+        assert_eq!(source.retry_at, Some(now + BREAKER_COOLDOWN));
+
+        // a single attempt is let through after the cooldown
+        now += BREAKER_COOLDOWN;
+        assert_eq!(source.poll_at(now, "test", || Ok(42)), Some(42));
+        assert_eq!(source.connection, Connection::Connected);
+        assert_eq!(source.failures, 0);
+        assert_eq!(source.error, None);
+        assert_eq!(source.retry_at, None);
+        assert_eq!(source.updates, 1);
+        assert!(!source.is_stale());
+    }
+}
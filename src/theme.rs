@@ -0,0 +1,315 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+};
+use serde::{Deserialize, Deserializer, de::Error as _};
+
+/// Chart markers telling the series apart in monochrome themes, with the
+/// symbol shown next to the series name.
+const MONOCHROME_MARKERS: [(Marker, &str); 4] = [
+    (Marker::Braille, "⣿"),
+    (Marker::Dot, "•"),
+    (Marker::Block, "█"),
+    (Marker::Bar, "▄"),
+];
+
+/// Modifiers telling the log sources apart in monochrome themes.
+const MONOCHROME_SOURCES: [Modifier; 6] = [
+    Modifier::BOLD,
+    Modifier::ITALIC,
+    Modifier::UNDERLINED,
+    Modifier::BOLD.union(Modifier::ITALIC),
+    Modifier::BOLD.union(Modifier::UNDERLINED),
+    Modifier::ITALIC.union(Modifier::UNDERLINED),
+];
+
+/// Colors of the UI.
+///
+/// Custom themes are read from the `[[themes]]` tables of the config, colors
+/// missing there are taken from the dark theme. Colors are given by name
+/// (`"light-cyan"`), as hex (`"#00ffcc"`) or as an ANSI index (`"122"`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    /// Focused borders, key hints and the first chart series
+    #[serde(deserialize_with = "color")]
+    pub accent: Color,
+    /// Text drawn on top of the accent color
+    #[serde(deserialize_with = "color")]
+    pub on_accent: Color,
+    /// Table headers, hints and other secondary text
+    #[serde(deserialize_with = "color")]
+    pub muted: Color,
+    #[serde(deserialize_with = "color")]
+    pub success: Color,
+    #[serde(deserialize_with = "color")]
+    pub warning: Color,
+    #[serde(deserialize_with = "color")]
+    pub error: Color,
+    /// Background of text matching a filter
+    #[serde(deserialize_with = "color")]
+    pub matched: Color,
+    /// Text drawn on top of the matched color
+    #[serde(deserialize_with = "color")]
+    pub on_matched: Color,
+    /// Series of comparison charts, one per network
+    #[serde(deserialize_with = "colors")]
+    pub series: Vec<Color>,
+    #[serde(deserialize_with = "color")]
+    pub cpu: Color,
+    #[serde(deserialize_with = "color")]
+    pub net_up: Color,
+    #[serde(deserialize_with = "color")]
+    pub net_down: Color,
+    /// Source of the transaction logs
+    #[serde(deserialize_with = "color")]
+    pub block_source: Color,
+    /// Sources of the MPC logs, assigned in order of appearance per network
+    #[serde(deserialize_with = "colors")]
+    pub sources: Vec<Color>,
+    /// Tell series, selection and sources apart by markers, symbols and
+    /// modifiers instead of color
+    pub monochrome: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            accent: Color::Indexed(122),
+            on_accent: Color::Black,
+            muted: Color::DarkGray,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            matched: Color::Yellow,
+            on_matched: Color::Black,
+            series: vec![
+                Color::Indexed(122),
+                Color::Indexed(213),
+                Color::Indexed(221),
+                Color::Indexed(147),
+            ],
+            cpu: Color::Indexed(122),
+            net_up: Color::Indexed(121),
+            net_down: Color::Indexed(123),
+            block_source: Color::Green,
+            sources: vec![
+                Color::Yellow,
+                Color::Cyan,
+                Color::Blue,
+                Color::Magenta,
+                Color::LightGreen,
+                Color::LightRed,
+                Color::LightBlue,
+                Color::LightMagenta,
+            ],
+            monochrome: false,
+        }
+    }
+
+    /// A theme for terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            accent: Color::Indexed(30),
+            on_accent: Color::White,
+            muted: Color::Indexed(244),
+            success: Color::Indexed(28),
+            warning: Color::Indexed(130),
+            error: Color::Indexed(160),
+            matched: Color::Indexed(222),
+            on_matched: Color::Black,
+            series: vec![
+                Color::Indexed(30),
+                Color::Indexed(127),
+                Color::Indexed(130),
+                Color::Indexed(61),
+            ],
+            cpu: Color::Indexed(30),
+            net_up: Color::Indexed(28),
+            net_down: Color::Indexed(25),
+            block_source: Color::Indexed(28),
+            sources: vec![
+                Color::Indexed(130),
+                Color::Indexed(31),
+                Color::Indexed(25),
+                Color::Indexed(127),
+                Color::Indexed(28),
+                Color::Indexed(160),
+                Color::Indexed(61),
+                Color::Indexed(90),
+            ],
+            monochrome: false,
+        }
+    }
+
+    /// A theme with bright colors on black and without dim text.
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            accent: Color::LightYellow,
+            on_accent: Color::Black,
+            muted: Color::Gray,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            matched: Color::White,
+            on_matched: Color::Black,
+            series: vec![
+                Color::LightYellow,
+                Color::LightCyan,
+                Color::LightMagenta,
+                Color::White,
+            ],
+            cpu: Color::LightYellow,
+            net_up: Color::LightCyan,
+            net_down: Color::LightMagenta,
+            block_source: Color::LightGreen,
+            sources: vec![
+                Color::LightYellow,
+                Color::LightCyan,
+                Color::LightGreen,
+                Color::LightMagenta,
+                Color::White,
+                Color::LightRed,
+                Color::LightBlue,
+                Color::Yellow,
+            ],
+            monochrome: false,
+        }
+    }
+
+    /// A theme without colors, for terminals that lack them or when
+    /// `NO_COLOR` is set.
+    pub fn monochrome() -> Self {
+        Self {
+            name: "monochrome".to_string(),
+            accent: Color::Reset,
+            on_accent: Color::Reset,
+            muted: Color::Reset,
+            success: Color::Reset,
+            warning: Color::Reset,
+            error: Color::Reset,
+            matched: Color::Reset,
+            on_matched: Color::Reset,
+            series: Vec::new(),
+            cpu: Color::Reset,
+            net_up: Color::Reset,
+            net_down: Color::Reset,
+            block_source: Color::Reset,
+            sources: Vec::new(),
+            monochrome: true,
+        }
+    }
+
+    /// The built-in themes followed by the `custom` ones.
+    pub fn all(custom: Vec<Self>) -> Vec<Self> {
+        let mut themes = vec![
+            Self::dark(),
+            Self::light(),
+            Self::high_contrast(),
+            Self::monochrome(),
+        ];
+        themes.extend(custom);
+        themes
+    }
+
+    /// Style of focused borders, key hints and prompts.
+    pub fn accent(&self) -> Style {
+        let style = Style::new().fg(self.accent);
+        if self.monochrome { style.bold() } else { style }
+    }
+
+    /// Style of selected menu items, table rows and list entries.
+    pub fn selected(&self) -> Style {
+        if self.monochrome {
+            Style::new().reversed().bold()
+        } else {
+            Style::new().bg(self.accent).fg(self.on_accent)
+        }
+    }
+
+    /// Style of table headers, hints and other secondary text.
+    pub fn muted(&self) -> Style {
+        if self.monochrome {
+            Style::new().dim()
+        } else {
+            Style::new().fg(self.muted)
+        }
+    }
+
+    /// Style of text matching a filter.
+    pub fn matched(&self) -> Style {
+        if self.monochrome {
+            Style::new().bold().underlined()
+        } else {
+            Style::new().bg(self.matched).fg(self.on_matched)
+        }
+    }
+
+    /// Style of the `index`th series of a comparison chart.
+    pub fn series(&self, index: usize) -> Style {
+        Style::new().fg(cycle(&self.series, index).unwrap_or(self.accent))
+    }
+
+    /// Chart marker of the `index`th series.
+    pub fn series_marker(&self, index: usize) -> Marker {
+        if self.monochrome {
+            MONOCHROME_MARKERS[index % MONOCHROME_MARKERS.len()].0
+        } else {
+            Marker::Braille
+        }
+    }
+
+    /// Returns `name` of the `index`th series, prefixed with the symbol of its
+    /// marker in monochrome themes.
+    pub fn series_label(&self, index: usize, name: &str) -> String {
+        if self.monochrome {
+            let (_, symbol) = MONOCHROME_MARKERS[index % MONOCHROME_MARKERS.len()];
+            format!("{symbol} {name}")
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Style of the `index`th color of the MPC log sources.
+    pub fn source(&self, index: usize) -> Style {
+        if self.monochrome {
+            Style::new().add_modifier(MONOCHROME_SOURCES[index % MONOCHROME_SOURCES.len()])
+        } else {
+            Style::new().fg(cycle(&self.sources, index).unwrap_or(self.accent))
+        }
+    }
+}
+
+/// Returns whether colors should be avoided, because `NO_COLOR` is set or the
+/// terminal cannot show them.
+pub fn colors_disabled() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+        || std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+fn cycle(colors: &[Color], index: usize) -> Option<Color> {
+    colors.get(index % colors.len().max(1)).copied()
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
+
+fn colors<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Color>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|color| color.parse().map_err(D::Error::custom))
+        .collect()
+}
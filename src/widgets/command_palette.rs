@@ -0,0 +1,234 @@
+use chrono::TimeDelta;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, List, ListItem, ListState, StatefulWidget, Widget},
+};
+
+use crate::{
+    export::ExportFormat,
+    keymap::Action,
+    screenshot::ScreenshotFormat,
+    theme::Theme,
+    widgets::text_input::{TextInput, TextInputState},
+};
+
+const WIDTH: u16 = 64;
+const MAX_RESULTS: u16 = 12;
+
+/// What happens when a command is chosen.
+#[derive(Debug, Clone)]
+pub enum CommandKind {
+    Page(usize),
+    Network(usize),
+    Wallet(usize),
+    /// Show the logs of a node
+    Node(String),
+    /// Show the logs of the last time range
+    Since(TimeDelta),
+    /// Show the charts of the last time range, or of the live window if unset
+    ChartRange(Option<TimeDelta>),
+    Theme(usize),
+    Screenshot(ScreenshotFormat),
+    /// Export the data shown on the selected page
+    Export(ExportFormat),
+    Action(Action),
+}
+
+pub struct Command {
+    pub label: String,
+    pub category: &'static str,
+    pub kind: CommandKind,
+}
+
+impl Command {
+    pub fn new(label: impl Into<String>, category: &'static str, kind: CommandKind) -> Self {
+        Self {
+            label: label.into(),
+            category,
+            kind,
+        }
+    }
+}
+
+pub struct CommandPaletteState {
+    pub input_state: TextInputState,
+    commands: Vec<Command>,
+    /// Indices of the commands matching the input, best match first, with
+    /// the positions of the matched characters
+    matches: Vec<(usize, Vec<usize>)>,
+    list_state: ListState,
+}
+
+impl CommandPaletteState {
+    pub fn new(commands: Vec<Command>) -> Self {
+        let mut state = Self {
+            input_state: TextInputState::default().hint_text("Type a page, wallet, node or action"),
+            commands,
+            matches: Vec::new(),
+            list_state: ListState::default(),
+        };
+        state.update_matches();
+        state
+    }
+
+    /// Types `key` into the input and matches the commands against it.
+    pub fn handle_input(&mut self, key: KeyEvent) {
+        self.input_state.handle_events(key.code, key.modifiers);
+        self.update_matches();
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            let selected = self.list_state.selected().unwrap_or_default();
+            self.list_state
+                .select(Some((selected + 1) % self.matches.len()));
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.matches.is_empty() {
+            let selected = self.list_state.selected().unwrap_or_default();
+            self.list_state.select(Some(
+                (selected + self.matches.len() - 1) % self.matches.len(),
+            ));
+        }
+    }
+
+    /// Returns the highlighted command.
+    pub fn selected(&self) -> Option<&Command> {
+        let (index, _) = self.matches.get(self.list_state.selected()?)?;
+        Some(&self.commands[*index])
+    }
+
+    fn update_matches(&mut self) {
+        let mut matches = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, command)| {
+                fuzzy_match(&self.input_state.text, &command.label)
+                    .map(|(score, positions)| (score, i, positions))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+        self.matches = matches
+            .into_iter()
+            .map(|(_, i, positions)| (i, positions))
+            .collect();
+        self.list_state
+            .select((!self.matches.is_empty()).then_some(0));
+    }
+}
+
+/// Returns a score for how well `text` matches `query` and the positions of
+/// the matched characters, if all characters of `query` appear in `text` in order.
+///
+/// Consecutive matches and matches at the start of a word score higher.
+fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let mut query = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .peekable();
+    let mut score = 0;
+    let mut positions = Vec::<usize>::new();
+    let mut previous = ' ';
+    for (i, c) in text.chars().enumerate() {
+        let Some(next) = query.peek() else {
+            break;
+        };
+        if c.to_ascii_lowercase() == *next {
+            score += 1;
+            if positions.last().is_some_and(|last| last + 1 == i) {
+                score += 5;
+            }
+            if !previous.is_alphanumeric() {
+                score += 3;
+            }
+            positions.push(i);
+            query.next();
+        }
+        previous = c;
+    }
+    query.peek().is_none().then_some((score, positions))
+}
+
+/// Popup at the top of the screen with the input and the matching commands.
+pub struct CommandPalette<'a> {
+    theme: &'a Theme,
+}
+
+impl<'a> CommandPalette<'a> {
+    pub fn new(theme: &'a Theme) -> Self {
+        Self { theme }
+    }
+}
+
+impl StatefulWidget for CommandPalette<'_> {
+    type State = CommandPaletteState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        // 2 for the borders, 2 for the input and the separator
+        let height = state.matches.len().min(usize::from(MAX_RESULTS)) as u16 + 4;
+        let [popup] = Layout::horizontal([Constraint::Length(WIDTH)])
+            .flex(Flex::Center)
+            .areas(area);
+        let popup = Rect::new(popup.x, area.y + 2, popup.width, height).intersection(area);
+
+        Clear.render(popup, buf);
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(self.theme.accent())
+            .title(" Commands ")
+            .render(popup, buf);
+        let [input_area, _, list_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(popup.inner(Margin::new(1, 1)));
+        let [prompt, input] =
+            Layout::horizontal([Constraint::Length(2), Constraint::Fill(1)]).areas(input_area);
+
+        Span::styled(":", self.theme.accent()).render(prompt, buf);
+        TextInput::default()
+            .hint_style(self.theme.muted())
+            .render_cursor(true)
+            .render(input, buf, &mut state.input_state);
+
+        let items = state
+            .matches
+            .iter()
+            .map(|(index, positions)| {
+                let command = &state.commands[*index];
+                let mut spans = command
+                    .label
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        if positions.contains(&i) {
+                            Span::styled(c.to_string(), Style::new().bold().underlined())
+                        } else {
+                            Span::raw(c.to_string())
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let padding = usize::from(list_area.width)
+                    .saturating_sub(command.label.chars().count() + command.category.len());
+                spans.push(Span::raw(" ".repeat(padding)));
+                spans.push(Span::styled(command.category, self.theme.muted()));
+                ListItem::new(Line::from(spans))
+            })
+            .collect::<Vec<_>>();
+        StatefulWidget::render(
+            List::new(items).highlight_style(self.theme.selected()),
+            list_area,
+            buf,
+            &mut state.list_state,
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{StatefulWidget, Widget},
+};
+
+use crate::{source::SourceStatus, theme::Theme};
+
+/// Renders a bordered widget and marks it when its source stopped
+/// delivering: the widget is dimmed and its top border tells how long ago
+/// the data was updated.
+pub struct Freshness<'a, W> {
+    widget: W,
+    status: &'a SourceStatus,
+    theme: &'a Theme,
+}
+
+impl<'a, W> Freshness<'a, W> {
+    pub fn new(widget: W, status: &'a SourceStatus, theme: &'a Theme) -> Self {
+        Self {
+            widget,
+            status,
+            theme,
+        }
+    }
+}
+
+impl<W: Widget> Widget for Freshness<'_, W> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.widget.render(area, buf);
+        mark_stale(area, buf, self.status, self.theme);
+    }
+}
+
+impl<W: StatefulWidget> StatefulWidget for Freshness<'_, W> {
+    type State = W::State;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        self.widget.render(area, buf, state);
+        mark_stale(area, buf, self.status, self.theme);
+    }
+}
+
+fn mark_stale(area: Rect, buf: &mut Buffer, status: &SourceStatus, theme: &Theme) {
+    if !status.is_stale() {
+        return;
+    }
+    buf.set_style(area, theme.muted());
+    let border = Rect::new(area.x + 1, area.y, area.width.saturating_sub(2), 1);
+    Line::styled(
+        format!(" stale, {} ", status.updated_ago()),
+        Style::new().fg(theme.warning).bold(),
+    )
+    .right_aligned()
+    .render(border.intersection(area), buf);
+}
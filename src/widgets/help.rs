@@ -0,0 +1,111 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Cell, Clear, Row, Table, Widget},
+};
+
+use crate::{
+    keymap::{Action, Context, KeySequence},
+    theme::Theme,
+};
+
+const WIDTH: u16 = 64;
+
+/// Popup listing the key bindings of the current page and focus context.
+pub struct Help<'a> {
+    context: Context,
+    bindings: Vec<(Action, Vec<&'a KeySequence>)>,
+    theme: &'a Theme,
+}
+
+impl<'a> Help<'a> {
+    pub fn new(
+        context: Context,
+        bindings: Vec<(Action, Vec<&'a KeySequence>)>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            context,
+            bindings,
+            theme,
+        }
+    }
+}
+
+impl Widget for Help<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // 2 for the borders, 2 for the header and its margin
+        let height = self.bindings.len() as u16 + 4;
+        let [popup] = Layout::horizontal([Constraint::Length(WIDTH)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(popup);
+
+        let header = ["Keys", "Action"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.theme.muted())
+            .bottom_margin(1);
+        let rows = self.bindings.into_iter().map(|(action, keys)| {
+            let keys = keys
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            Row::new(vec![
+                Cell::from(keys).style(self.theme.accent().bold()),
+                Cell::from(action.description()),
+            ])
+        });
+
+        Clear.render(popup, buf);
+        Table::new(rows, [Constraint::Length(24), Constraint::Fill(1)])
+            .header(header)
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .border_style(self.theme.accent())
+                    .title(format!(" Keys: {} ", self.context))
+                    .title_bottom(Line::from(" press any key to close ").right_aligned()),
+            )
+            .render(popup, buf);
+    }
+}
+
+/// One line footer with the first key of the most important bindings.
+pub struct KeyHints<'a> {
+    bindings: Vec<(Action, Vec<&'a KeySequence>)>,
+    theme: &'a Theme,
+}
+
+impl<'a> KeyHints<'a> {
+    pub fn new(bindings: Vec<(Action, Vec<&'a KeySequence>)>, theme: &'a Theme) -> Self {
+        Self { bindings, theme }
+    }
+}
+
+impl Widget for KeyHints<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.inner(Margin::new(1, 0));
+        let mut spans = Vec::new();
+        let mut width = 0;
+        for (action, keys) in self.bindings {
+            let key = keys[0].to_string();
+            let hint_width = key.len() + action.label().len() + 3;
+            if width + hint_width > usize::from(area.width) {
+                break;
+            }
+            width += hint_width;
+            spans.push(Span::styled(key, self.theme.accent().bold()));
+            spans.push(Span::styled(
+                format!(" {}  ", action.label()),
+                self.theme.muted(),
+            ));
+        }
+        Line::from(spans).render(area, buf);
+    }
+}
@@ -0,0 +1,406 @@
+use std::{collections::HashMap, mem, ops::Range};
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyEvent, MouseButton, MouseEventKind},
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, List, ListItem, StatefulWidget, Widget},
+};
+
+use crate::{
+    keymap::Action,
+    logs::{BLOCK_SOURCE_PREFIX, LogEntry, LogFilter, LogLevel},
+    theme::Theme,
+    widgets::text_input::{TextInput, TextInputState},
+};
+
+/// Pause, scroll position and selection in the logs of one network.
+#[derive(Default)]
+struct View {
+    paused: Option<Vec<LogEntry>>,
+    /// Number of entries scrolled back from the newest one, the view is
+    /// paused while scrolled back so that it stays on the same entries
+    offset: usize,
+    /// Index of the clicked entry in the filtered entries
+    selected: Option<usize>,
+}
+
+pub struct LogViewState {
+    pub focused: bool,
+    pub filter_focused: bool,
+    pub filter_state: TextInputState,
+    filter_query: String,
+    filter: LogFilter,
+    /// Position in the logs of the selected network
+    view: View,
+    /// Positions in the logs of the other networks, by network index
+    views: HashMap<usize, View>,
+    area: Rect,
+    list_area: Rect,
+    visible: Range<usize>,
+}
+
+impl Default for LogViewState {
+    fn default() -> Self {
+        Self {
+            focused: false,
+            filter_focused: false,
+            filter_state: TextInputState::default().hint_text("node:<name> level:<level> <regex>"),
+            filter_query: String::new(),
+            filter: LogFilter::default(),
+            view: View::default(),
+            views: HashMap::new(),
+            area: Rect::default(),
+            list_area: Rect::default(),
+            visible: 0..0,
+        }
+    }
+}
+
+impl LogViewState {
+    pub fn is_paused(&self) -> bool {
+        self.view.paused.is_some()
+    }
+
+    /// Keeps the pause, scroll position and selection in the logs of the
+    /// `previous` network and restores those of the `current` one.
+    pub fn on_network_change(&mut self, previous: usize, current: usize) {
+        let view = self.views.remove(&current).unwrap_or_default();
+        self.views
+            .insert(previous, mem::replace(&mut self.view, view));
+    }
+
+    /// Freezes the view on the current `entries` or resumes live updates.
+    pub fn toggle_pause(&mut self, entries: &[LogEntry]) {
+        self.view.paused = match self.view.paused {
+            Some(_) => None,
+            None => Some(entries.to_vec()),
+        };
+        self.view.selected = None;
+    }
+
+    /// Handles a mouse event at `position` in the coordinates the panel was
+    /// rendered in, returns whether it hit the panel.
+    ///
+    /// Clicking an entry focuses the panel, pauses it and selects the entry.
+    pub fn handle_mouse(
+        &mut self,
+        kind: MouseEventKind,
+        position: Position,
+        entries: &[LogEntry],
+    ) -> bool {
+        if !self.area.contains(position) {
+            return false;
+        }
+        match kind {
+            MouseEventKind::ScrollUp => self.scroll_back(1, entries),
+            MouseEventKind::ScrollDown => self.view.offset = self.view.offset.saturating_sub(1),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.focused = true;
+                if self.list_area.contains(position) {
+                    let index = self.visible.start + usize::from(position.y - self.list_area.y);
+                    if index < self.visible.end {
+                        if !self.is_paused() {
+                            self.toggle_pause(entries);
+                        }
+                        self.view.selected = Some(index);
+                    }
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Handles `action` while the panel is focused, returns whether it was consumed.
+    pub fn handle_action(&mut self, action: Action, entries: &[LogEntry]) -> bool {
+        if self.filter_focused {
+            match action {
+                Action::Unfocus | Action::Submit => self.filter_focused = false,
+                // the arrow keys must not scroll the page while typing
+                Action::ScrollUp | Action::ScrollDown => {}
+                _ => return false,
+            }
+            return true;
+        }
+        match action {
+            Action::TogglePause => self.toggle_pause(entries),
+            Action::ScrollDown => self.view.offset = self.view.offset.saturating_sub(1),
+            Action::ScrollUp => self.scroll_back(1, entries),
+            Action::Top => self.scroll_back(usize::MAX, entries),
+            Action::Bottom => {
+                self.view.offset = 0;
+                self.view.paused = None;
+                self.view.selected = None;
+            }
+            Action::FocusSearch => self.filter_focused = true,
+            Action::Unfocus => self.focused = false,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Scrolls back by `lines` entries, pausing the view on the current
+    /// `entries` first.
+    fn scroll_back(&mut self, lines: usize, entries: &[LogEntry]) {
+        if !self.is_paused() {
+            self.toggle_pause(entries);
+        }
+        self.view.offset = self.view.offset.saturating_add(lines);
+    }
+
+    /// Types `key` into the filter bar.
+    pub fn handle_input(&mut self, key: KeyEvent) {
+        self.filter_state.handle_events(key.code, key.modifiers);
+        self.view.offset = 0;
+    }
+
+    fn update_filter(&mut self) {
+        if self.filter_state.text != self.filter_query {
+            self.filter_query.clone_from(&self.filter_state.text);
+            self.filter = LogFilter::parse(&self.filter_query);
+            self.view.selected = None;
+        }
+    }
+}
+
+/// Log panel with pause, scrollback and a filter bar shared by the log widgets.
+pub struct LogView<'a> {
+    title: &'a str,
+    entries: &'a [LogEntry],
+    /// Sources of the network in order of appearance, see [`source_style`]
+    sources: &'a [String],
+    theme: &'a Theme,
+}
+
+impl<'a> LogView<'a> {
+    pub fn new(title: &'a str, entries: &'a [LogEntry], theme: &'a Theme) -> Self {
+        Self {
+            title,
+            entries,
+            sources: &[],
+            theme,
+        }
+    }
+
+    pub fn sources(mut self, sources: &'a [String]) -> Self {
+        self.sources = sources;
+        self
+    }
+}
+
+impl StatefulWidget for LogView<'_> {
+    type State = LogViewState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let border_style = if state.focused {
+            self.theme.accent()
+        } else {
+            Style::new()
+        };
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(border_style)
+            .render(area, buf);
+
+        let [top, filter_area, bot] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(area.inner(Margin::new(1, 1)));
+
+        state.update_filter();
+        let entries = state.view.paused.as_deref().unwrap_or(self.entries);
+        let filtered = entries
+            .iter()
+            .filter(|entry| state.filter.matches(entry))
+            .collect::<Vec<_>>();
+
+        let visible = usize::from(bot.height);
+        state.view.offset = state
+            .view
+            .offset
+            .min(filtered.len().saturating_sub(visible));
+        let end = filtered.len() - state.view.offset;
+        let start = end.saturating_sub(visible);
+        state.area = area;
+        state.list_area = bot;
+        state.visible = start..end;
+
+        let mut title = vec![Span::styled(self.title, Style::new().bold())];
+        let errors = entries
+            .iter()
+            .filter(|entry| entry.level == LogLevel::Error)
+            .count();
+        if errors > 0 {
+            title.push(Span::styled(
+                format!(" {errors} errors"),
+                Style::new().fg(self.theme.error).bold(),
+            ));
+        }
+        if state.is_paused() {
+            title.push(Span::styled(
+                " [paused]",
+                Style::new().fg(self.theme.warning),
+            ));
+        }
+        if !state.filter.is_empty() {
+            title.push(Span::styled(
+                format!(" {}/{} matching", filtered.len(), entries.len()),
+                self.theme.muted(),
+            ));
+        }
+        if state.view.offset > 0 {
+            title.push(Span::styled(
+                format!(" +{} newer", state.view.offset),
+                self.theme.muted(),
+            ));
+        }
+        if let Some(err) = &state.filter.error {
+            title.push(Span::styled(
+                format!(" invalid filter: {err}"),
+                Style::new().fg(self.theme.error),
+            ));
+        }
+        Line::from(title).render(top, buf);
+
+        if state.filter_focused || !state.filter_query.is_empty() {
+            let [prompt, input] =
+                Layout::horizontal([Constraint::Length(2), Constraint::Fill(1)]).areas(filter_area);
+            Span::styled("/", self.theme.accent()).render(prompt, buf);
+            TextInput::default()
+                .hint_style(self.theme.muted())
+                .render_cursor(state.filter_focused)
+                .render(input, buf, &mut state.filter_state);
+        }
+
+        let logs: Vec<ListItem> = filtered[start..end]
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let mut spans = vec![
+                    Span::styled(
+                        entry.timestamp.format("%H:%M:%S ").to_string(),
+                        self.theme.muted(),
+                    ),
+                    level_badge(entry.level, self.theme),
+                    Span::raw(" "),
+                    Span::styled(
+                        entry.source.as_str(),
+                        source_style(&entry.source, self.sources, self.theme),
+                    ),
+                    Span::raw(": "),
+                ];
+                spans.extend(highlight(
+                    &entry.message,
+                    state.filter.hits(&entry.message),
+                    self.theme,
+                ));
+                let item = ListItem::new(Line::from(spans));
+                if state.view.selected == Some(start + i) {
+                    item.style(self.theme.selected())
+                } else {
+                    item
+                }
+            })
+            .collect();
+        Widget::render(List::new(logs), bot, buf);
+    }
+}
+
+pub fn level_style(level: LogLevel, theme: &Theme) -> Style {
+    match level {
+        LogLevel::Debug => theme.muted(),
+        LogLevel::Info => Style::new().fg(theme.success),
+        LogLevel::Warn => Style::new().fg(theme.warning).bold(),
+        LogLevel::Error => Style::new().fg(theme.error).bold(),
+    }
+}
+
+/// Style of the log `source`, blocks in the block color and nodes in the
+/// palette color of their position in the `sources` of the network, so that a
+/// node has the same color on every page.
+pub fn source_style(source: &str, sources: &[String], theme: &Theme) -> Style {
+    if source.starts_with(BLOCK_SOURCE_PREFIX) {
+        return Style::new().fg(theme.block_source);
+    }
+    let index = sources
+        .iter()
+        .position(|known| known == source)
+        .unwrap_or(sources.len());
+    theme.source(index)
+}
+
+fn level_badge(level: LogLevel, theme: &Theme) -> Span<'static> {
+    Span::styled(format!("{level:<5}"), level_style(level, theme))
+}
+
+fn highlight<'a>(message: &'a str, hits: Vec<Range<usize>>, theme: &Theme) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for hit in hits {
+        spans.push(Span::raw(&message[pos..hit.start]));
+        spans.push(Span::styled(&message[hit.clone()], theme.matched()));
+        pos = hit.end;
+    }
+    spans.push(Span::raw(&message[pos..]));
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{Network, NetworkConfig};
+
+    fn network(name: &str) -> Network {
+        Network::new(
+            0,
+            NetworkConfig {
+                name: name.to_string(),
+                outages: false,
+            },
+        )
+    }
+
+    #[test]
+    fn sources_are_told_apart_in_monochrome() {
+        let theme = Theme::monochrome();
+        let network = network("Base");
+        assert_eq!(network.log_sources.len(), 4);
+        let styles: Vec<_> = network
+            .log_sources
+            .iter()
+            .map(|source| source_style(source, &network.log_sources, &theme))
+            .collect();
+        for (i, style) in styles.iter().enumerate() {
+            assert!(!styles[..i].contains(style), "{:?}", network.log_sources);
+        }
+    }
+
+    #[test]
+    fn sources_are_styled_in_order_of_appearance() {
+        let theme = Theme::dark();
+        let sources = ["MPC Node 2".to_string(), "MPC Coordinator".to_string()];
+        assert_eq!(
+            source_style("MPC Node 2", &sources, &theme),
+            theme.source(0)
+        );
+        assert_eq!(
+            source_style("MPC Coordinator", &sources, &theme),
+            theme.source(1)
+        );
+        // another network keeps its own order
+        let other = ["MPC Coordinator".to_string()];
+        assert_eq!(
+            source_style("MPC Coordinator", &other, &theme),
+            theme.source(0)
+        );
+        assert_eq!(
+            source_style("block-7", &sources, &theme),
+            Style::new().fg(theme.block_source)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use ratatui::{buffer::Buffer, layout::Rect, text::Text, widgets::Widget};
+use ratatui_image::{
+    Image, Resize,
+    picker::{Picker, ProtocolType},
+    protocol::Protocol,
+};
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::theme::Theme;
+
+/// The logo, embedded so the app can be started from any directory.
+const LOGO_PNG: &[u8] = include_bytes!("../../logo.png");
+
+/// The logo for terminals that cannot show images.
+const ASCII_LOGO: [&str; 3] = ["  //", " // ", "//  "];
+
+/// How the logo is drawn, every mode falls back to the next one when the
+/// terminal does not support it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogoMode {
+    /// Kitty, Sixel or iTerm2 graphics, whichever the terminal supports
+    #[default]
+    Image,
+    /// Colored unicode half blocks
+    Halfblocks,
+    Ascii,
+    /// No logo, only the title
+    Text,
+}
+
+pub enum Logo {
+    Image(Protocol),
+    Ascii,
+    Text,
+}
+
+impl Logo {
+    /// Creates the logo drawn with `mode`, or with the first fallback the
+    /// terminal supports. Half blocks need `colors`.
+    pub fn new(mode: LogoMode, colors: bool) -> Self {
+        let picker = match mode {
+            LogoMode::Image => Picker::from_query_stdio().unwrap_or_else(|err| {
+                debug!("cannot query terminal graphics, using half blocks: {err}");
+                Picker::halfblocks()
+            }),
+            LogoMode::Halfblocks => Picker::halfblocks(),
+            LogoMode::Ascii => return Self::Ascii,
+            LogoMode::Text => return Self::Text,
+        };
+        if picker.protocol_type() == ProtocolType::Halfblocks && !colors {
+            return Self::Ascii;
+        }
+        let logo = image::load_from_memory(LOGO_PNG)
+            .inspect_err(|err| warn!("cannot decode logo: {err}"))
+            .ok()
+            .and_then(|image| {
+                picker
+                    .new_protocol(image, Rect::new(0, 0, 4, 4), Resize::Scale(None))
+                    .inspect_err(|err| warn!("cannot encode logo: {err}"))
+                    .ok()
+            });
+        debug!(protocol = ?picker.protocol_type(), drawn = logo.is_some(), "logo");
+        logo.map_or(Self::Ascii, Self::Image)
+    }
+
+    /// Columns taken by the logo left of the title.
+    pub fn width(&self) -> u16 {
+        match self {
+            Self::Image(_) | Self::Ascii => 5,
+            Self::Text => 0,
+        }
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        match self {
+            Self::Image(protocol) => Image::new(protocol).render(area, buf),
+            Self::Ascii => Text::from_iter(ASCII_LOGO)
+                .style(theme.accent())
+                .render(area, buf),
+            Self::Text => {}
+        }
+    }
+}
@@ -1,9 +1,17 @@
 pub mod avg_proof_time;
 pub mod avg_tx_latency;
+pub mod command_palette;
+pub mod freshness;
+pub mod help;
+pub mod log_view;
+pub mod logo;
 pub mod mpc_log;
 pub mod network_util;
 pub mod node_util;
+pub mod status_bar;
 pub mod text_input;
+pub mod toasts;
+pub mod tooltip;
 pub mod total_txs;
 pub mod txs_log;
 pub mod txs_per_second;
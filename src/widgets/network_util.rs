@@ -7,19 +7,20 @@ use ratatui::{
 };
 use ratatui_circle_gauge::CircleGauge;
 
-use crate::ACCENT_COLOR;
+use crate::theme::Theme;
 
-pub struct NetworkUtil {
+pub struct NetworkUtil<'a> {
     value: f64,
+    theme: &'a Theme,
 }
 
-impl NetworkUtil {
-    pub fn new(value: f64) -> Self {
-        Self { value }
+impl<'a> NetworkUtil<'a> {
+    pub fn new(value: f64, theme: &'a Theme) -> Self {
+        Self { value, theme }
     }
 }
 
-impl Widget for NetworkUtil {
+impl Widget for NetworkUtil<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         Block::bordered()
             .border_type(BorderType::Rounded)
@@ -43,8 +44,8 @@ impl Widget for NetworkUtil {
         CircleGauge::default()
             .ratio(self.value.clamp(0.0, 1.0))
             .stroke(5.0)
-            .fill_style(Style::new().fg(ACCENT_COLOR))
-            .empty_style(Style::new().dark_gray())
+            .fill_style(self.theme.accent())
+            .empty_style(self.theme.muted())
             .render(bot, buf);
     }
 }
@@ -1,19 +1,22 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Margin, Rect},
-    style::{Color, Modifier, Style, Stylize as _},
-    symbols::Marker,
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    style::{Modifier, Style, Stylize as _},
     text::{Line, Span},
     widgets::{Axis, Block, BorderType, Chart, Dataset, Paragraph, Widget},
 };
 
-use crate::ACCENT_COLOR;
+use crate::{
+    theme::Theme,
+    widgets::tooltip::{graph_area, nearest, x_at},
+};
 
 pub struct NodeUtil<'a> {
     window: [f64; 2],
     cpu: &'a [(f64, f64)],
     net_up: &'a [(f64, f64)],
     net_down: &'a [(f64, f64)],
+    theme: &'a Theme,
 }
 
 impl<'a> NodeUtil<'a> {
@@ -22,13 +25,50 @@ impl<'a> NodeUtil<'a> {
         cpu: &'a [(f64, f64)],
         net_up: &'a [(f64, f64)],
         net_down: &'a [(f64, f64)],
+        theme: &'a Theme,
     ) -> Self {
         Self {
             window,
             cpu,
             net_up,
             net_down,
+            theme,
+        }
+    }
+
+    fn layout(area: Rect) -> [Rect; 2] {
+        Layout::vertical([Constraint::Length(4), Constraint::Fill(1)])
+            .areas(area.inner(Margin::new(1, 1)))
+    }
+
+    /// Returns the tooltip lines for the data points under `position`, if it
+    /// is within the chart of the widget rendered in `area`.
+    pub fn tooltip(&self, area: Rect, position: Position) -> Option<Vec<Line<'static>>> {
+        let graph = graph_area(Self::layout(area)[1]);
+        if !graph.contains(position) {
+            return None;
+        }
+        let x = x_at(graph, self.window, position.x);
+        let mut lines = vec![Line::styled(format!("x = {x:.1}"), Style::new().bold())];
+        for (i, (name, unit, data, color)) in [
+            ("CPU Usage", "%", self.cpu, self.theme.cpu),
+            ("Network Up", "Mbps", self.net_up, self.theme.net_up),
+            ("Network Down", "Mbps", self.net_down, self.theme.net_down),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            if let Some((_, y)) = nearest(data, x) {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", self.theme.series_label(i, name)),
+                        Style::default().fg(color),
+                    ),
+                    Span::raw(format!("{y:.2} {unit}")),
+                ]));
+            }
         }
+        Some(lines)
     }
 }
 
@@ -38,8 +78,7 @@ impl Widget for NodeUtil<'_> {
             .border_type(BorderType::Rounded)
             .render(area, buf);
 
-        let [top, bot] = Layout::vertical([Constraint::Length(4), Constraint::Fill(1)])
-            .areas(area.inner(Margin::new(1, 1)));
+        let [top, bot] = Self::layout(area);
         let [top_left, top_right] =
             Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(top);
 
@@ -68,19 +107,19 @@ impl Widget for NodeUtil<'_> {
         ];
         let datasets = vec![
             Dataset::default()
-                .name("CPU Usage in %")
-                .marker(Marker::Braille)
-                .style(Style::default().fg(ACCENT_COLOR))
+                .name(self.theme.series_label(0, "CPU Usage in %"))
+                .marker(self.theme.series_marker(0))
+                .style(Style::default().fg(self.theme.cpu))
                 .data(self.cpu),
             Dataset::default()
-                .name("Network Up in Mbps")
-                .marker(Marker::Braille)
-                .style(Style::default().fg(Color::Indexed(121)))
+                .name(self.theme.series_label(1, "Network Up in Mbps"))
+                .marker(self.theme.series_marker(1))
+                .style(Style::default().fg(self.theme.net_up))
                 .data(self.net_up),
             Dataset::default()
-                .name("Network Down in Mbps")
-                .marker(Marker::Braille)
-                .style(Style::default().fg(Color::Indexed(123)))
+                .name(self.theme.series_label(2, "Network Down in Mbps"))
+                .marker(self.theme.series_marker(2))
+                .style(Style::default().fg(self.theme.net_down))
                 .data(self.net_down),
         ];
 
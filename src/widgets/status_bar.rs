@@ -0,0 +1,105 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::{
+    network::Network,
+    source::{Connection, SourceStatus, format_age},
+    theme::Theme,
+};
+
+/// One line bar with the connection of every source of the selected network
+/// and the worst connection of the other networks, followed by the last error
+/// of the selected network.
+pub struct StatusBar<'a> {
+    networks: &'a [Network],
+    selected: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> StatusBar<'a> {
+    pub fn new(networks: &'a [Network], selected: usize, theme: &'a Theme) -> Self {
+        Self {
+            networks,
+            selected,
+            theme,
+        }
+    }
+
+    /// Returns the symbol and style of `source`, stale connected sources are
+    /// hollow.
+    fn indicator(&self, source: &SourceStatus) -> Span<'static> {
+        let (symbol, color) = match source.connection {
+            Connection::Connected if source.is_stale() => ("○", self.theme.warning),
+            Connection::Connected => ("●", self.theme.success),
+            Connection::Reconnecting => ("◌", self.theme.warning),
+            Connection::Failed => ("✕", self.theme.error),
+        };
+        Span::styled(symbol, Style::new().fg(color))
+    }
+}
+
+impl Widget for StatusBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = Vec::new();
+        let selected = &self.networks[self.selected];
+        spans.push(Span::styled(
+            selected.name.as_str(),
+            self.theme.accent().bold(),
+        ));
+        for source in &selected.sources {
+            spans.push(Span::raw("  "));
+            spans.push(self.indicator(source));
+            let label = match (source.connection, source.retry_in()) {
+                (Connection::Connected, _) => format!(" {}", source.kind.name()),
+                (connection, Some(retry_in)) => format!(
+                    " {} {}, retry in {}",
+                    source.kind.name(),
+                    connection.name(),
+                    format_age(retry_in)
+                ),
+                (connection, None) => format!(" {} {}", source.kind.name(), connection.name()),
+            };
+            spans.push(Span::styled(label, self.theme.muted()));
+        }
+        let error = selected
+            .sources
+            .iter()
+            .filter_map(|source| Some((source.kind.name(), source.error.as_deref()?)))
+            .chain(
+                selected
+                    .history_error
+                    .as_deref()
+                    .map(|err| ("history", err)),
+            )
+            .next();
+        for (i, network) in self.networks.iter().enumerate() {
+            if i == self.selected {
+                continue;
+            }
+            // the source with the worst connection, preferring stale ones
+            let Some(worst) = network
+                .sources
+                .iter()
+                .max_by_key(|source| (source.connection, source.is_stale()))
+            else {
+                continue;
+            };
+            spans.push(Span::styled("  │  ", self.theme.muted()));
+            spans.push(Span::raw(format!("{} ", network.name)));
+            spans.push(self.indicator(worst));
+        }
+        if let Some((name, err)) = error {
+            spans.push(Span::styled("  │  ", self.theme.muted()));
+            spans.push(Span::styled(
+                format!("{name}: {err}"),
+                Style::new().fg(self.theme.error),
+            ));
+        }
+        Line::from(spans).render(area.inner(Margin::new(1, 0)), buf);
+    }
+}
@@ -0,0 +1,60 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+use crate::{
+    alerts::{AlertEvent, AlertEventKind},
+    theme::Theme,
+};
+
+const WIDTH: u16 = 48;
+const HEIGHT: u16 = 4;
+
+/// Stack of alert notifications rendered over the top right corner of `area`.
+pub struct Toasts<'a> {
+    events: &'a [AlertEvent],
+    theme: &'a Theme,
+}
+
+impl<'a> Toasts<'a> {
+    pub fn new(events: &'a [AlertEvent], theme: &'a Theme) -> Self {
+        Self { events, theme }
+    }
+}
+
+impl Widget for Toasts<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = WIDTH.min(area.width);
+        let mut y = area.y;
+        for event in self.events {
+            if y + HEIGHT > area.bottom() {
+                break;
+            }
+            let toast = Rect::new(area.right() - width, y, width, HEIGHT);
+            let (title, color) = match event.kind {
+                AlertEventKind::Firing => (" Alert firing ", self.theme.error),
+                AlertEventKind::Resolved => (" Alert resolved ", self.theme.success),
+            };
+            Clear.render(toast, buf);
+            Paragraph::new(vec![
+                Line::styled(
+                    format!("{} [{}]", event.rule, event.network),
+                    Style::new().bold(),
+                ),
+                Line::raw(format!("{} (value {:.2})", event.condition, event.value)),
+            ])
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::new().fg(color))
+                    .title(title),
+            )
+            .render(toast, buf);
+            y += HEIGHT;
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+/// Width of the y axis labels and the axis line of the dashboard charts.
+const Y_AXIS_WIDTH: u16 = 4;
+/// Height of the x axis labels and the axis line of the dashboard charts.
+const X_AXIS_HEIGHT: u16 = 2;
+
+/// Popup with the exact values under the mouse cursor, placed next to `anchor`
+/// and kept within the render area.
+pub struct Tooltip<'a> {
+    lines: Vec<Line<'a>>,
+    anchor: Position,
+}
+
+impl<'a> Tooltip<'a> {
+    pub fn new(lines: Vec<Line<'a>>, anchor: Position) -> Self {
+        Self { lines, anchor }
+    }
+}
+
+impl Widget for Tooltip<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = self.lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2;
+        let height = self.lines.len() as u16 + 2;
+        let x = if self.anchor.x + 2 + width <= area.right() {
+            self.anchor.x + 2
+        } else {
+            self.anchor.x.saturating_sub(width + 1).max(area.x)
+        };
+        let y = if self.anchor.y + 1 + height <= area.bottom() {
+            self.anchor.y + 1
+        } else {
+            self.anchor.y.saturating_sub(height).max(area.y)
+        };
+        let popup = Rect::new(x, y, width, height).intersection(area);
+        Clear.render(popup, buf);
+        Paragraph::new(self.lines)
+            .block(Block::bordered().border_type(BorderType::Rounded))
+            .render(popup, buf);
+    }
+}
+
+/// Returns the plotting area of a chart rendered in `area`, without its axes.
+pub fn graph_area(area: Rect) -> Rect {
+    Rect::new(
+        area.x + Y_AXIS_WIDTH,
+        area.y,
+        area.width.saturating_sub(Y_AXIS_WIDTH),
+        area.height.saturating_sub(X_AXIS_HEIGHT),
+    )
+}
+
+/// Returns the x value of `column` in a graph spanning `window`.
+pub fn x_at(graph: Rect, window: [f64; 2], column: u16) -> f64 {
+    let ratio = f64::from(column.saturating_sub(graph.x)) / f64::from(graph.width.max(1));
+    window[0] + ratio * (window[1] - window[0])
+}
+
+/// Returns the data point closest to `x`.
+pub fn nearest(data: &[(f64, f64)], x: f64) -> Option<(f64, f64)> {
+    data.iter()
+        .copied()
+        .min_by(|a, b| (a.0 - x).abs().total_cmp(&(b.0 - x).abs()))
+}
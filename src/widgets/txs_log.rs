@@ -1,43 +1,26 @@
-use ratatui::{
-    buffer::Buffer,
-    layout::{Constraint, Layout, Margin, Rect},
-    style::{Color, Style},
-    text::{Line, Span},
-    widgets::{Block, BorderType, List, ListItem, Widget},
+use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+
+use crate::{
+    logs::LogEntry,
+    theme::Theme,
+    widgets::log_view::{LogView, LogViewState},
 };
 
 pub struct TxsLog<'a> {
-    logs: &'a [(String, String)],
+    logs: &'a [LogEntry],
+    theme: &'a Theme,
 }
 
 impl<'a> TxsLog<'a> {
-    pub fn new(logs: &'a [(String, String)]) -> Self {
-        Self { logs }
+    pub fn new(logs: &'a [LogEntry], theme: &'a Theme) -> Self {
+        Self { logs, theme }
     }
 }
 
-impl Widget for TxsLog<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        Block::bordered()
-            .border_type(BorderType::Rounded)
-            .render(area, buf);
-
-        let [top, bot] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)])
-            .spacing(1)
-            .areas(area.inner(Margin::new(1, 1)));
-        Line::styled("Transaction Logs", Style::new().bold()).render(top, buf);
+impl StatefulWidget for TxsLog<'_> {
+    type State = LogViewState;
 
-        let logs: Vec<ListItem> = self
-            .logs
-            .iter()
-            .map(|(block, msg)| {
-                let content = vec![Line::from(vec![
-                    Span::styled(block, Style::default().fg(Color::Green)),
-                    Span::raw(format!(": {msg}")),
-                ])];
-                ListItem::new(content)
-            })
-            .collect();
-        List::new(logs).render(bot, buf);
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        LogView::new("Transaction Logs", self.logs, self.theme).render(area, buf, state);
     }
 }
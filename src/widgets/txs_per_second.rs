@@ -1,22 +1,77 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Margin, Rect},
+    layout::{Constraint, Layout, Margin, Position, Rect},
     style::{Modifier, Style, Stylize as _},
-    symbols::Marker,
     text::{Line, Span},
     widgets::{Axis, Block, BorderType, Chart, Dataset, Paragraph, Widget, Wrap},
 };
 
-use crate::ACCENT_COLOR;
+use crate::{
+    theme::Theme,
+    widgets::tooltip::{graph_area, nearest, x_at},
+};
 
 pub struct TxsPerSecond<'a> {
     window: [f64; 2],
-    data: &'a [(f64, f64)],
+    series: Vec<(&'a str, &'a [(f64, f64)])>,
+    theme: &'a Theme,
 }
 
 impl<'a> TxsPerSecond<'a> {
-    pub fn new(window: [f64; 2], data: &'a [(f64, f64)]) -> Self {
-        Self { window, data }
+    pub fn new(window: [f64; 2], data: &'a [(f64, f64)], theme: &'a Theme) -> Self {
+        Self {
+            window,
+            series: vec![("", data)],
+            theme,
+        }
+    }
+
+    /// Overlays the named series in distinct colors with a legend.
+    pub fn compare(
+        window: [f64; 2],
+        series: Vec<(&'a str, &'a [(f64, f64)])>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            window,
+            series,
+            theme,
+        }
+    }
+
+    fn is_comparison(&self) -> bool {
+        self.series.len() > 1
+    }
+
+    fn layout(area: Rect) -> [Rect; 2] {
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .areas(area.inner(Margin::new(1, 1)))
+    }
+
+    /// Returns the tooltip lines for the data points under `position`, if it
+    /// is within the chart of the widget rendered in `area`.
+    pub fn tooltip(&self, area: Rect, position: Position) -> Option<Vec<Line<'a>>> {
+        let graph = graph_area(Self::layout(area)[1]);
+        if !graph.contains(position) {
+            return None;
+        }
+        let x = x_at(graph, self.window, position.x);
+        let mut lines = vec![Line::styled(format!("x = {x:.1}"), Style::new().bold())];
+        for (i, (name, data)) in self.series.iter().enumerate() {
+            let Some((_, y)) = nearest(data, x) else {
+                continue;
+            };
+            let mut spans = Vec::new();
+            if self.is_comparison() {
+                spans.push(Span::styled(
+                    format!("{} ", self.theme.series_label(i, name)),
+                    self.theme.series(i),
+                ));
+            }
+            spans.push(Span::raw(format!("{y:.2} tx/s")));
+            lines.push(Line::from(spans));
+        }
+        Some(lines)
     }
 }
 
@@ -26,11 +81,12 @@ impl Widget for TxsPerSecond<'_> {
             .border_type(BorderType::Rounded)
             .render(area, buf);
 
-        let [left, right] =
-            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
-                .areas(area.inner(Margin::new(1, 1)));
-        let [left_top, left_bot] =
-            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(left);
+        let [left, right] = Self::layout(area);
+        let [left_top, left_bot] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(self.series.len() as u16),
+        ])
+        .areas(left);
 
         Paragraph::new(vec![
             Line::styled("Transactions", Style::new().bold()),
@@ -39,15 +95,27 @@ impl Widget for TxsPerSecond<'_> {
         .wrap(Wrap::default())
         .render(left_top, buf);
 
-        Line::default()
-            .spans(vec![
-                Span::styled(
-                    format!("{:.2}", self.data.last().copied().unwrap_or_default().1),
+        let values = self
+            .series
+            .iter()
+            .enumerate()
+            .map(|(i, (name, data))| {
+                let mut spans = Vec::new();
+                if self.is_comparison() {
+                    spans.push(Span::styled(
+                        format!("{} ", self.theme.series_label(i, name)),
+                        self.theme.series(i),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!("{:.2}", data.last().copied().unwrap_or_default().1),
                     Style::default().bold(),
-                ),
-                Span::raw(" tx/s"),
-            ])
-            .render(left_bot, buf);
+                ));
+                spans.push(Span::raw(" tx/s"));
+                Line::default().spans(spans)
+            })
+            .collect::<Vec<_>>();
+        Paragraph::new(values).render(left_bot, buf);
 
         let x_labels = vec![
             Span::styled(
@@ -60,12 +128,23 @@ impl Widget for TxsPerSecond<'_> {
                 Style::default().add_modifier(Modifier::BOLD),
             ),
         ];
-        let datasets = vec![
-            Dataset::default()
-                .marker(Marker::Braille)
-                .style(Style::default().fg(ACCENT_COLOR))
-                .data(self.data),
-        ];
+        let datasets = self
+            .series
+            .iter()
+            .enumerate()
+            .map(|(i, (name, data))| {
+                let dataset = Dataset::default()
+                    .marker(self.theme.series_marker(i))
+                    .data(data);
+                if self.is_comparison() {
+                    dataset
+                        .name(self.theme.series_label(i, name))
+                        .style(self.theme.series(i))
+                } else {
+                    dataset.style(self.theme.accent())
+                }
+            })
+            .collect::<Vec<_>>();
 
         Chart::new(datasets)
             .x_axis(Axis::default().labels(x_labels).bounds(self.window))
@@ -74,6 +153,7 @@ impl Widget for TxsPerSecond<'_> {
                     .labels(["-20".bold(), "0".into(), "20".bold()])
                     .bounds([-20.0, 20.0]),
             )
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Min(0)))
             .render(right, buf);
     }
 }
@@ -1,35 +1,92 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Margin, Rect},
-    style::{Color, Style},
+    layout::{Constraint, Layout, Margin, Position, Rect},
     text::{Line, Span},
-    widgets::{Block, BorderType, Cell, Row, StatefulWidget, Table, Widget},
+    widgets::{Block, BorderType, Cell, Row, StatefulWidget, Table, TableState, Widget},
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    ACCENT_COLOR,
+    theme::Theme,
     widgets::text_input::{TextInput, TextInputState},
 };
 
+/// Lines taken by the top border and the header of the table.
+const HEADER_HEIGHT: u16 = 1 + 4;
+/// Lines taken by a row of the table including its margin.
+const ROW_HEIGHT: u16 = 4;
+
 pub struct WalletsTableState {
     pub search_state: TextInputState,
     pub search_focused: bool,
+    pub table_state: TableState,
+    search_area: Rect,
+    table_area: Rect,
+    rows: usize,
+}
+
+impl WalletsTableState {
+    pub fn new(search_state: TextInputState) -> Self {
+        Self {
+            search_state,
+            search_focused: false,
+            table_state: TableState::default(),
+            search_area: Rect::default(),
+            table_area: Rect::default(),
+            rows: 0,
+        }
+    }
+
+    /// Handles a click at `position` in the coordinates the table was rendered
+    /// in, returns whether it hit the search box or a row.
+    pub fn click(&mut self, position: Position) -> bool {
+        if self.search_area.contains(position) {
+            self.search_focused = true;
+            return true;
+        }
+        let first_row = self.table_area.y + HEADER_HEIGHT;
+        if !self.table_area.contains(position) || position.y < first_row {
+            return false;
+        }
+        let index = self.table_state.offset() + usize::from((position.y - first_row) / ROW_HEIGHT);
+        if index >= self.rows {
+            return false;
+        }
+        self.search_focused = false;
+        self.table_state.select(Some(index));
+        true
+    }
 }
 
-pub struct WalletsTable {}
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Wallet {
+    pub address: String,
+    pub last_transfer: String,
+    pub transferred_amount: u64,
+    pub balance: u64,
+}
+
+pub struct WalletsTable<'a> {
+    wallets: &'a [&'a Wallet],
+    theme: &'a Theme,
+}
 
-impl WalletsTable {
-    pub fn new() -> Self {
-        Self {}
+impl<'a> WalletsTable<'a> {
+    pub fn new(wallets: &'a [&'a Wallet], theme: &'a Theme) -> Self {
+        Self { wallets, theme }
     }
 }
 
-impl StatefulWidget for WalletsTable {
+impl StatefulWidget for WalletsTable<'_> {
     type State = WalletsTableState;
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let [search_area, table_area] =
             Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
         let [search_area] = Layout::horizontal([Constraint::Max(50)]).areas(search_area);
+        state.search_area = search_area;
+        state.table_area = table_area;
+        state.rows = self.wallets.len();
 
         Block::bordered()
             .border_type(BorderType::Rounded)
@@ -47,24 +104,24 @@ impl StatefulWidget for WalletsTable {
             .into_iter()
             .map(Cell::from)
             .collect::<Row>()
-            .style(Style::new().fg(Color::DarkGray))
+            .style(self.theme.muted())
             .top_margin(1)
             .height(3);
-        let rows = (0..20)
-            .map(|i| {
-                let wallet = format!("0x1234...{:04x}", i);
-                let last_transfer = format!("2024/09/{:02} 12:34", i + 1);
+        let rows = self
+            .wallets
+            .iter()
+            .map(|wallet| {
                 let transferred_amount = Line::default().spans([
-                    Span::raw(format!("{}\t\t\t", (i + 1) * 1000)),
-                    Span::styled(" ", Style::default().fg(ACCENT_COLOR)),
+                    Span::raw(format!("{}\t\t\t", wallet.transferred_amount)),
+                    Span::styled(" ", self.theme.accent()),
                 ]);
                 let balance = Line::default().spans([
-                    Span::raw(format!("{}\t\t\t", (20 - i) * 5000)),
-                    Span::styled(" ", Style::default().fg(ACCENT_COLOR)),
+                    Span::raw(format!("{}\t\t\t", wallet.balance)),
+                    Span::styled(" ", self.theme.accent()),
                 ]);
                 Row::new(vec![
-                    Cell::from(wallet),
-                    Cell::from(last_transfer),
+                    Cell::from(wallet.address.as_str()),
+                    Cell::from(wallet.last_transfer.as_str()),
                     Cell::from(transferred_amount),
                     Cell::from(balance),
                 ])
@@ -81,7 +138,8 @@ impl StatefulWidget for WalletsTable {
 
         let table = Table::new(rows, widths)
             .header(header)
-            .block(Block::bordered().border_type(BorderType::Rounded));
-        Widget::render(table, table_area, buf);
+            .block(Block::bordered().border_type(BorderType::Rounded))
+            .row_highlight_style(self.theme.selected());
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
     }
 }